@@ -4,8 +4,63 @@ use anchor_lang::prelude::*;
 pub enum SolscatterError {
     #[msg("number of rewards in drawing result must be greater than 0")]
     NumberOfRewardsMustMoreThanZero,
-    NumberOfRewardsMustLessOrEqualTen,
-    #[msg("number of random numbers must equal to number of rewards")]
-    NumberOfRandomNumbersNotMatchWithNumberOfRewards,
+    TooManyRewards,
     InvalidSwitchboardVrfAccount,
+    #[msg("withdraw amount must not exceed the deposited amount")]
+    WithdrawAmountExceedsDeposit,
+    MathOverflow,
+    #[msg("no fresh vrf result for this round")]
+    VrfResultNotReady,
+    #[msg("drawing result is not finished")]
+    DrawingNotFinished,
+    InvalidRewardIndex,
+    #[msg("signer is not the winner of this reward")]
+    NotWinner,
+    #[msg("prize has already been claimed")]
+    PrizeAlreadyClaimed,
+    #[msg("user deposit account does not match the expected slot")]
+    InvalidUserDepositAccount,
+    #[msg("deposit tree has no free slot left")]
+    DepositTreeFull,
+    RandomNumberOutOfRange,
+    #[msg("winning user deposit account was not passed to drawing")]
+    MissingWinnerUserDeposit,
+    #[msg("signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("this operation is paused")]
+    Paused,
+    #[msg("current round has not ended yet")]
+    RoundNotEnded,
+    #[msg("deposits are closed until the current round is drawn")]
+    DepositClosed,
+    #[msg("round duration must be greater than 0 and longer than the deposit cutoff")]
+    InvalidRoundDuration,
+    #[msg("deposits are frozen while a drawing is in progress")]
+    DrawingInProgress,
+    #[msg("prize tiers must be between 1 and 5 entries and split at most 100% of the prize pool")]
+    InvalidPrizeTiers,
+    #[msg("winners page does not belong to this drawing")]
+    InvalidWinnersPage,
+    #[msg("claim window must be positive")]
+    InvalidClaimWindow,
+    #[msg("the claim window of this drawing has closed")]
+    ClaimExpired,
+    #[msg("the claim window of this drawing is still open")]
+    ClaimWindowOpen,
+    #[msg("fee must not exceed 10000 bps")]
+    InvalidFee,
+    #[msg("deposit must be empty to change its type")]
+    DepositNotEmpty,
+    #[msg("yield source is not supported")]
+    UnsupportedYieldSource,
+    #[msg("accounts do not match the pool's yield source")]
+    InvalidYieldSourceAccounts,
+    #[msg("quarry miner is already initialized")]
+    MinerAlreadyInitialized,
+    #[msg("pool is not denominated in SOL")]
+    NotSolPool,
+    #[msg("a user deposit account is passed more than once")]
+    DuplicateUserDeposit,
+    #[msg("winners page does not hold the next reward to draw")]
+    DrawingOutOfOrder,
 }
\ No newline at end of file
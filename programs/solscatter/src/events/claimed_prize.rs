@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ClaimedPrizeEvent {
+    #[index]
+    pub round: u64,
+    pub reward_index: u16,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub claimed_timestamp: i64,
+}
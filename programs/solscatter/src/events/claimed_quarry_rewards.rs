@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ClaimedQuarryRewardsEvent {
+    #[index]
+    pub round: u64,
+    pub rewards_mint: Pubkey,
+    pub claimed_amount: u64,
+    /// Rewards staked into the vault, zero unless they are in the underlying mint.
+    pub staked_amount: u64,
+    pub claimed_timestamp: i64,
+}
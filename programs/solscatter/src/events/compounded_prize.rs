@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CompoundedPrizeEvent {
+    #[index]
+    pub round: u64,
+    pub reward_index: u16,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
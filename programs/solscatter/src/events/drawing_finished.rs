@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DrawingFinishedEvent {
+    #[index]
+    pub round: u64,
+    pub aborted: bool,
+    /// Prize pool carried into the next round.
+    pub rollover_amount: u64,
+    pub finished_timestamp: i64,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct DrawingStartedEvent {
+    #[index]
+    pub round: u64,
+    pub prize_amount: u64,
+    /// Part of the prize pool carried over from earlier rounds.
+    pub rollover_amount: u64,
+    pub started_timestamp: i64,
+}
@@ -1,3 +1,15 @@
 pub mod received_vrf;
+pub mod claimed_prize;
+pub mod compounded_prize;
+pub mod drawing_started;
+pub mod drawing_finished;
+pub mod protocol_fee;
+pub mod claimed_quarry_rewards;
 
-pub use received_vrf::*;
\ No newline at end of file
+pub use received_vrf::*;
+pub use claimed_prize::*;
+pub use compounded_prize::*;
+pub use drawing_started::*;
+pub use drawing_finished::*;
+pub use protocol_fee::*;
+pub use claimed_quarry_rewards::*;
\ No newline at end of file
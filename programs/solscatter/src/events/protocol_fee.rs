@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ProtocolFeeEvent {
+    #[index]
+    pub round: u64,
+    pub fee_amount: u64,
+    /// Fees taken from the round's yield so far, including this one.
+    pub round_fee_amount: u64,
+    pub harvested_timestamp: i64,
+}
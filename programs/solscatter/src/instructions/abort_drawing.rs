@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::events::DrawingFinishedEvent;
+use crate::state::{
+    main_state::MainState,
+    drawing_result::{DrawingResult, DrawingState},
+    DepositTree,
+};
+use crate::MAIN_STATE_SEED;
+
+/// Abandons a drawing that cannot finish, rolling its prize into the next round.
+/// Prizes already compounded into deposits stay with their winners.
+#[derive(Accounts)]
+pub struct AbortDrawing<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+    )]
+    pub main_state: Account<'info, MainState>,
+    #[account(
+        mut,
+        seeds = [
+            b"drawing_result",
+            main_state.key().as_ref(),
+            main_state.current_round.to_le_bytes().as_ref(),
+        ],
+        bump,
+        constraint = drawing_result.state == DrawingState::Processing,
+    )]
+    pub drawing_result: Account<'info, DrawingResult>,
+    #[account(
+        mut,
+        address = main_state.deposit_tree,
+    )]
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
+    pub admin: Signer<'info>,
+}
+
+/// Closes `drawing_result` as aborted and moves the pool on to the next round.
+pub(crate) fn abort(
+    main_state: &mut MainState,
+    drawing_result: &mut DrawingResult,
+    deposit_tree: &mut DepositTree,
+    now: i64,
+) {
+    drawing_result.state = DrawingState::Aborted;
+    drawing_result.finished_timestamp = Some(now);
+
+    main_state.roll_over(drawing_result.prize_amount - drawing_result.compounded_amount);
+    main_state.drawing_in_progress = false;
+    main_state.start_next_round(now);
+    deposit_tree.reset_twabs();
+}
+
+pub fn handler(ctx: Context<AbortDrawing>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let drawing_result = &mut ctx.accounts.drawing_result;
+    let main_state = &mut ctx.accounts.main_state;
+    abort(main_state, drawing_result, &mut *ctx.accounts.deposit_tree.load_mut()?, now);
+
+    emit!(DrawingFinishedEvent {
+        round: drawing_result.round,
+        aborted: true,
+        rollover_amount: main_state.rollover_amount,
+        finished_timestamp: now,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::record_deposit;
+    use crate::state::fixtures::{as_tree, new_drawing_result, new_main_state, new_tree, new_user_deposit};
+
+    #[test]
+    fn aborting_rolls_over_the_prizes_left_in_the_pool() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        let mut main_state = new_main_state();
+        record_deposit(&mut main_state, &mut new_user_deposit(1), tree, 100, 500).unwrap();
+        // a prize of 100 is being drawn with 20 left over, 30 of it is already compounded
+        main_state.drawing_in_progress = true;
+        main_state.prize_pool = 20;
+        main_state.rollover_amount = 20;
+        main_state.unclaimed_prize = 70;
+        let mut drawing_result = new_drawing_result();
+        drawing_result.state = DrawingState::Processing;
+        drawing_result.finished_timestamp = None;
+        drawing_result.prize_amount = 100;
+        drawing_result.compounded_amount = 30;
+
+        abort(&mut main_state, &mut drawing_result, tree, 1_200);
+
+        assert_eq!(drawing_result.state, DrawingState::Aborted);
+        assert_eq!(drawing_result.finished_timestamp, Some(1_200));
+        assert_eq!(main_state.unclaimed_prize, 0);
+        assert_eq!(main_state.prize_pool, 90);
+        assert_eq!(main_state.rollover_amount, 90);
+        assert!(!main_state.drawing_in_progress);
+        assert_eq!(main_state.current_round, 2);
+        assert_eq!((main_state.round_start_timestamp, main_state.round_end_timestamp), (1_000, 2_000));
+        assert_eq!(tree.total_twab(), 100);
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{
+    error::SolscatterError,
+    state::main_state::MainState,
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = main_state.pending_admin == Some(pending_admin.key()) @ SolscatterError::NotPendingAdmin,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub pending_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.admin = ctx.accounts.pending_admin.key();
+    main_state.pending_admin = None;
+    Ok(())
+}
@@ -1,14 +1,39 @@
 use anchor_lang::prelude::*;
 use switchboard_v2::VrfAccountData;
 
-use crate::{state::VrfClientState, events::ReceivedVrfEvent};
+use crate::{
+    error::SolscatterError,
+    events::ReceivedVrfEvent,
+    state::{main_state::MainState, VrfClientState},
+    MAIN_STATE_SEED,
+    SWITCHBOARD_PROGRAM_ID,
+};
 
+/// Only the VRF account registered with `state` and its pool, owned by Switchboard, can write a result.
+/// The first result produced after the round ended is locked in for its drawing, later ones are ignored.
 #[derive(Accounts)]
 pub struct CallbackRequestRandomness<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = state.load()?.vrf == vrf.key() @ SolscatterError::InvalidSwitchboardVrfAccount,
+        constraint = state.load()?.pool == main_state.key() @ SolscatterError::InvalidSwitchboardVrfAccount,
+    )]
     pub state: AccountLoader<'info, VrfClientState>,
     /// CHECK: this is vrf acocunt data
+    #[account(
+        constraint = *vrf.owner == SWITCHBOARD_PROGRAM_ID @ SolscatterError::InvalidSwitchboardVrfAccount,
+    )]
     pub vrf: AccountInfo<'info>,
+    #[account(
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = main_state.vrf_account_pubkey == vrf.key() @ SolscatterError::InvalidSwitchboardVrfAccount,
+    )]
+    pub main_state: Account<'info, MainState>,
 }
 
 pub fn handler(ctx: Context<CallbackRequestRandomness>) -> Result<()> {
@@ -21,6 +46,11 @@ pub fn handler(ctx: Context<CallbackRequestRandomness>) -> Result<()> {
     }
 
     let state = &mut ctx.accounts.state.load_mut()?;
+    if state.has_round_result(ctx.accounts.main_state.round_end_timestamp) {
+        msg!("result of this round is already locked in");
+        return Ok(());
+    }
+
     let max_result = state.max_result;
     if result_buffer == state.result_buffer {
         msg!("existing result_buffer");
@@ -0,0 +1,114 @@
+use crate::{
+    error::SolscatterError,
+    events::ClaimedPrizeEvent,
+    state::{
+        drawing_result::DrawingResult,
+        drawing_winners_page::{DrawingWinnersPage, WINNERS_PER_PAGE},
+        main_state::{MainState, PAUSE_CLAIM},
+    },
+    yield_source::PoolVault,
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Remaining accounts: the accounts of the pool's [YieldSource](crate::yield_source::YieldSource) adapter.
+#[derive(Accounts)]
+pub struct ClaimPrize<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"drawing_result",
+            main_state.key().as_ref(),
+            drawing_result.round.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub drawing_result: Account<'info, DrawingResult>,
+    /// The page holding `reward_index`.
+    #[account(
+        mut,
+        constraint = winners_page.load()?.drawing_result == drawing_result.key() @ SolscatterError::InvalidWinnersPage,
+    )]
+    pub winners_page: AccountLoader<'info, DrawingWinnersPage>,
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_CLAIM) @ SolscatterError::Paused,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub winner: Signer<'info>,
+
+    /// Program-owned vault paying out the prize.
+    #[account(
+        mut,
+        address = main_state.vault,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount] receiving the prize in the pool's underlying mint.
+    #[account(
+        mut,
+        constraint = destination_tokens.owner == winner.key(),
+    )]
+    pub destination_tokens: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ClaimPrizeParams {
+    pub reward_index: u16,
+}
+
+impl<'info> ClaimPrize<'info> {
+    pub fn validate(&self, params: &ClaimPrizeParams) -> Result<()> {
+        self.drawing_result.validate_claim(params.reward_index, Clock::get()?.unix_timestamp)?;
+
+        let winners_page = self.winners_page.load()?;
+        if winners_page.page_index as usize != params.reward_index as usize / WINNERS_PER_PAGE {
+            return Err(error!(SolscatterError::InvalidWinnersPage));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the amount of the prize claimed.
+    fn update_state(&mut self, reward_index: u16) -> Result<u64> {
+        let winners_page = &mut self.winners_page.load_mut()?;
+        let first_reward_index = winners_page.first_reward_index();
+        let amount = winners_page.entries[reward_index as usize - first_reward_index].claim(self.winner.key())?;
+
+        let main_state = &mut self.main_state;
+        main_state.unclaimed_prize = main_state.unclaimed_prize.saturating_sub(amount);
+        Ok(amount)
+    }
+
+    fn transfer_prize(&self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let yield_source = PoolVault::new(&self.main_state, &self.vault, self.token_program.to_account_info())
+            .load_yield_source(remaining_accounts)?;
+        yield_source.withdraw(&self.destination_tokens.to_account_info(), amount)
+    }
+
+    pub fn claim_prize(&mut self, params: ClaimPrizeParams, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let amount = self.update_state(params.reward_index)?;
+        self.transfer_prize(amount, remaining_accounts)?;
+
+        emit!(ClaimedPrizeEvent {
+            round: self.drawing_result.round,
+            reward_index: params.reward_index,
+            winner: self.winner.key(),
+            amount,
+            claimed_timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimPrize<'info>>, params: ClaimPrizeParams) -> Result<()> {
+    ctx.accounts.claim_prize(params, ctx.remaining_accounts)
+}
@@ -0,0 +1,123 @@
+use crate::{
+    MAIN_STATE_SEED,
+    QUARRY_REWARDS_SEED,
+    error::SolscatterError,
+    events::ClaimedQuarryRewardsEvent,
+    state::main_state::MainState,
+    yield_source::{PoolVault, YieldSource},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use quarry_mine::{
+    cpi::accounts::{ClaimRewards, UserClaim},
+    program::QuarryMine,
+    Miner, Quarry, Rewarder,
+};
+
+/// Permissionless crank claiming the mining rewards of the pool's [Miner].
+/// Rewards in the underlying mint are staked into the vault, where the next harvest adds them to the prize pool.
+/// Rewards in any other mint stay in the program-owned rewards account until `withdraw_quarry_rewards` moves them out for swapping.
+///
+/// Remaining accounts: the accounts of the pool's [YieldSource] adapter, only needed to stake the rewards.
+#[derive(Accounts)]
+pub struct ClaimQuarryRewards<'info> {
+    #[account(
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = vault,
+        has_one = miner,
+        constraint = main_state.yield_source == YieldSource::Quarry @ SolscatterError::UnsupportedYieldSource,
+    )]
+    pub main_state: Account<'info, MainState>,
+    #[account(mut)]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut)]
+    pub miner: Box<Account<'info, Miner>>,
+    #[account(mut)]
+    pub quarry: Box<Account<'info, Quarry>>,
+    pub rewarder: Box<Account<'info, Rewarder>>,
+    /// CHECK: Validated by the quarry mine program
+    #[account(mut)]
+    pub mint_wrapper: UncheckedAccount<'info>,
+    /// CHECK: Validated by the quarry mine program
+    pub mint_wrapper_program: UncheckedAccount<'info>,
+    /// CHECK: Validated by the quarry mine program
+    #[account(mut)]
+    pub minter: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub rewards_token_mint: Box<Account<'info, Mint>>,
+    /// Program-owned account receiving the mining rewards
+    #[account(
+        mut,
+        seeds = [QUARRY_REWARDS_SEED, main_state.key().as_ref()],
+        bump,
+    )]
+    pub rewards_tokens: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Validated by the quarry mine program
+    #[account(mut)]
+    pub claim_fee_token_account: UncheckedAccount<'info>,
+    pub quarry_mine_program: Program<'info, QuarryMine>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimQuarryRewards<'info> {
+    fn into_claim_rewards_context(&self) -> CpiContext<'_, '_, '_, 'info, ClaimRewards<'info>> {
+        CpiContext::new(
+            self.quarry_mine_program.to_account_info(),
+            ClaimRewards {
+                mint_wrapper: self.mint_wrapper.to_account_info(),
+                mint_wrapper_program: self.mint_wrapper_program.to_account_info(),
+                minter: self.minter.to_account_info(),
+                rewards_token_mint: self.rewards_token_mint.to_account_info(),
+                rewards_token_account: self.rewards_tokens.to_account_info(),
+                claim_fee_token_account: self.claim_fee_token_account.to_account_info(),
+                stake: UserClaim {
+                    authority: self.main_state.to_account_info(),
+                    miner: self.miner.to_account_info(),
+                    quarry: self.quarry.to_account_info(),
+                    unused_miner_vault: self.vault.to_account_info(),
+                    unused_token_account: self.vault.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    rewarder: self.rewarder.to_account_info(),
+                },
+            },
+        )
+    }
+
+    fn claim_rewards(&self) -> Result<()> {
+        let signer_seeds = self.main_state.signer_seeds();
+        quarry_mine::cpi::claim_rewards(self.into_claim_rewards_context().with_signer(&[&signer_seeds]))
+    }
+
+    pub fn claim_quarry_rewards(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let previous_amount = self.rewards_tokens.amount;
+        self.claim_rewards()?;
+        self.rewards_tokens.reload()?;
+        let claimed_amount = self.rewards_tokens.amount.saturating_sub(previous_amount);
+
+        let mut staked_amount = 0;
+        if self.rewards_tokens.mint == self.main_state.underlying_mint && self.rewards_tokens.amount > 0 {
+            staked_amount = self.rewards_tokens.amount;
+            let yield_source = PoolVault::new(&self.main_state, &self.vault, self.token_program.to_account_info())
+                .load_yield_source(remaining_accounts)?;
+            yield_source.deposit_from_pool(&self.rewards_tokens.to_account_info(), staked_amount)?;
+        }
+
+        emit!(ClaimedQuarryRewardsEvent {
+            round: self.main_state.current_round,
+            rewards_mint: self.rewards_tokens.mint,
+            claimed_amount,
+            staked_amount,
+            claimed_timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimQuarryRewards<'info>>) -> Result<()> {
+    ctx.accounts.claim_quarry_rewards(ctx.remaining_accounts)
+}
@@ -1,40 +1,44 @@
 use crate::{
-    state::{main_state::MainState, user_deposit::UserDeposit},
+    error::SolscatterError,
+    state::{main_state::{MainState, PAUSE_DEPOSIT}, user_deposit::UserDeposit, DepositTree},
+    yield_source::PoolVault,
     MAIN_STATE_SEED,
 };
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
-use yi::{cpi::accounts::Stake, YiToken};
+use anchor_spl::token::{Token, TokenAccount};
 
+/// Remaining accounts: the accounts of the pool's [YieldSource](crate::yield_source::YieldSource) adapter.
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
         mut,
         has_one = owner,
+        constraint = user_deposit.pool == main_state.key() @ SolscatterError::InvalidUserDepositAccount,
     )]
     pub user_deposit: Account<'info, UserDeposit>,
     #[account(
         mut,
-        seeds = [MAIN_STATE_SEED],
-        bump,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_DEPOSIT) @ SolscatterError::Paused,
     )]
     pub main_state: Account<'info, MainState>,
+    #[account(
+        mut,
+        address = main_state.deposit_tree,
+    )]
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
     pub owner: Signer<'info>,
     pub clock: Sysvar<'info, Clock>,
 
-    // solUST mint = 5fjG31cbSszE6FodW37UJnNzgVTyqg5WHWGCmL3ayAvA
-    // yi-solUST mint = 6XyygxFmUeemaTvA9E9mhH9FvgpynZqARVyG3gUdCMt7
-    /// CHECK: yi token program
-    #[account(address = yi::program::Yi::id())]
-    pub yi_token_program: AccountInfo<'info>,
-    /// CHECK: sol_ust_authority
-    pub sol_ust_authority: AccountLoader<'info, YiToken>,
-    /// [YiToken::mint]. [Mint] of the [YiToken].
-    #[account(mut)]
-    pub yi_mint: Account<'info, Mint>,
-    /// Tokens to be staked into the [YiToken].
+    /// Tokens to be deposited, in the pool's underlying mint.
     #[account(
         mut,
+        constraint = source_tokens.mint == main_state.underlying_mint,
         // associated_token::mint = "5fjG31cbSszE6FodW37UJnNzgVTyqg5WHWGCmL3ayAvA".parse::<Pubkey>(),
         // associated_token::authority = source_authority.to_account_info().key(),
     )]
@@ -42,40 +46,13 @@ pub struct Deposit<'info> {
     /// Thes [TokenAccount::owner] of [Self::source_tokens].
     #[account(mut)]
     pub source_authority: Signer<'info>,
-    /// [YiToken::underlying_tokens].
+    /// Program-owned vault receiving the deposit.
     #[account(
         mut,
-        // associated_token::mint = "5fjG31cbSszE6FodW37UJnNzgVTyqg5WHWGCmL3ayAvA".parse::<Pubkey>(),
-        // associated_token::authority = sol_ust_authority.to_account_info().key(),
+        address = main_state.vault,
     )]
-    pub yi_underlying_tokens: Box<Account<'info, TokenAccount>>,
-    /// The [TokenAccount] receiving the minted [YiToken]s.
-    #[account(
-        mut,
-        // associated_token::mint = "6XyygxFmUeemaTvA9E9mhH9FvgpynZqARVyG3gUdCMt7".parse::<Pubkey>(),
-        // associated_token::authority = source_authority.to_account_info().key(),
-    )]
-    pub destination_yi_tokens: Box<Account<'info, TokenAccount>>,
+    pub vault: Box<Account<'info, TokenAccount>>,
     pub token_program: Program<'info, Token>,
-    // Quarry
-    // #[account(
-    //     seeds = [
-    //         b"Miner".as_ref(),
-    //         quarry.key().as_ref(),
-    //         source_authority.key().as_ref(),
-    //     ],
-    //     bump,
-    // )]
-    // pub miner: Box<Account<'info, Miner>>,
-    // pub quarry_mine_program: Box<Program<'info, QuarryMine>>,
-    // #[account(mut)]
-    // pub quarry: Box<Account<'info, quarry_mine::Quarry>>,
-    // // [Rewarder]: 57fEKyj9C7dhdcrFCXFGQZp68CmjfVS4sgNecy2PdnTC
-    // pub rewarder: Box<Account<'info, Rewarder>>,
-    // pub system_program: Box<Program<'info, System>>,
-    // pub token_mint: Box<Account<'info, Mint>>,
-    // #[account(mut)]
-    // pub miner_vault: Box<Account<'info, TokenAccount>>,
 }
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -84,80 +61,131 @@ pub struct DepositParams {
 }
 
 impl<'info> Deposit<'info> {
-    fn into_stake_cpi_context(&self) -> CpiContext<'_, '_, '_, 'info, Stake<'info>> {
-        CpiContext::new(
-            self.yi_token_program.to_account_info(),
-            Stake {
-                yi_token: self.sol_ust_authority.to_account_info(),
-                yi_mint: self.yi_mint.to_account_info(),
-                source_tokens: self.source_tokens.to_account_info(),
-                source_authority: self.source_authority.to_account_info(),
-                yi_underlying_tokens: self.yi_underlying_tokens.to_account_info(),
-                destination_yi_tokens: self.destination_yi_tokens.to_account_info(),
-                token_program: self.token_program.to_account_info(),
-            },
+    fn stake(&self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let yield_source = PoolVault::new(&self.main_state, &self.vault, self.token_program.to_account_info())
+            .load_yield_source(remaining_accounts)?;
+        yield_source.deposit(
+            &self.source_tokens.to_account_info(),
+            &self.source_authority.to_account_info(),
+            amount,
         )
     }
 
-    // fn into_create_miner_context(&self) -> CpiContext<'_, '_, '_, 'info, CreateMiner<'info>> {
-    //     let (miner_pda, miner_bump) = Pubkey::find_program_address(
-    //         &[
-    //             b"Miner",
-    //             self.quarry.to_account_info().key().as_ref(),
-    //             self.source_authority.to_account_info().key().as_ref(),
-    //         ],
-    //         &quarry_mine::id(),
-    //     );
-
-    //     CpiContext::new(
-    //         // quarry_mine: QMNeHCGYnLVDn1icRAfQZpjPLBNkfGbSKRB83G5d8KB
-    //         quarry_mine::id(),
-    //         CreateMiner {
-    //             authority: self.source_authority.to_account_info(),
-    //             miner: self.miner.to_account_info(),
-    //             quarry: self.quarry.to_account_info(),
-    //             rewarder: self.rewarder.to_account_info(),
-    //             system_program: self.system_program.to_account_info(),
-    //             payer: self.source_authority.to_account_info(),
-    //             token_mint: self.token_mint.to_account_info(),
-    //             miner_vault: self.miner_vault.to_account_info(),
-    //             token_program: self.token_program.to_account_info(),
-    //         },
-    //     )
-    // }
-
-    fn update_state(&mut self, amount: u64) -> Result<()> {
-        let user_deposit = &mut self.user_deposit;
-        user_deposit.amount = user_deposit.amount + amount;
-        user_deposit.latest_deposit_timestamp = Some(self.clock.unix_timestamp);
-
-        let main_state = &mut self.main_state;
-        main_state.total_deposit = main_state.total_deposit + amount;
+    pub fn deposit(&mut self, params: DepositParams, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        record_deposit(
+            &mut self.main_state,
+            &mut self.user_deposit,
+            &mut *self.deposit_tree.load_mut()?,
+            params.amount,
+            self.clock.unix_timestamp,
+        )?;
+        self.stake(params.amount, remaining_accounts)?;
         Ok(())
     }
+}
 
-    fn stake_sol_ust(&self, amount: u64) -> Result<()> {
-        yi::cpi::stake(self.into_stake_cpi_context(), amount)
+/// Credits `amount` to `user_deposit` once the pool accepts deposits, whatever the tokens are staked from.
+pub(crate) fn record_deposit(
+    main_state: &mut MainState,
+    user_deposit: &mut UserDeposit,
+    deposit_tree: &mut DepositTree,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if main_state.drawing_in_progress {
+        return Err(error!(SolscatterError::DrawingInProgress));
     }
 
-    // fn create_miner(&self, ctx: &Context<Self>) -> Result<()> {
-    //     // quarry
-    //     let bump = *ctx.bumps.get("miner").unwrap();
-    //     quarry_mine::cpi::create_miner(self.into_create_miner_context(), bump)
-    // }
+    if main_state.is_deposit_closed(now) {
+        return Err(error!(SolscatterError::DepositClosed));
+    }
 
-    pub fn deposit(&mut self, params: DepositParams) -> Result<()> {
-        self.update_state(params.amount)?;
-        self.stake_sol_ust(params.amount)?;
-        // self.create_miner(ctx)?;
-        Ok(())
+    if user_deposit.is_sponsor {
+        user_deposit.amount += amount;
+        user_deposit.latest_deposit_timestamp = Some(now);
+        main_state.total_sponsor_deposit += amount;
+        return Ok(());
     }
+
+    let twab = main_state.calculate_twab(amount, now);
+
+    user_deposit.sync_twab(main_state.current_round);
+    user_deposit.amount += amount;
+    user_deposit.twab += twab;
+    user_deposit.latest_deposit_timestamp = Some(now);
+
+    main_state.total_deposit += amount;
+
+    deposit_tree.add(user_deposit.slot, amount, twab)?;
+    Ok(())
 }
 
-pub fn handler(ctx: Context<Deposit>, params: DepositParams) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Deposit<'info>>, params: DepositParams) -> Result<()> {
     if params.amount == 0 {
         return Ok(());
     }
 
-    ctx.accounts.deposit(params)
+    ctx.accounts.deposit(params, ctx.remaining_accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::record_withdrawal;
+    use crate::state::fixtures::{as_tree, assert_error, new_main_state, new_tree, new_user_deposit};
+
+    #[test]
+    fn sponsor_deposits_stay_out_of_the_drawing() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        let mut main_state = new_main_state();
+        let mut sponsor = new_user_deposit(1);
+        sponsor.is_sponsor = true;
+        let mut player = new_user_deposit(2);
+
+        record_deposit(&mut main_state, &mut sponsor, tree, 500, 0).unwrap();
+        record_deposit(&mut main_state, &mut player, tree, 100, 0).unwrap();
+
+        assert_eq!(sponsor.amount, 500);
+        assert!(!sponsor.has_twab(main_state.current_round));
+        assert_eq!(main_state.total_sponsor_deposit, 500);
+        assert_eq!(main_state.total_deposit, 100);
+        assert_eq!(tree.total_twab(), 100);
+        assert_eq!((tree.find_slot(0).unwrap(), tree.find_slot(99).unwrap()), (2, 2));
+
+        record_withdrawal(&mut main_state, &mut sponsor, tree, 200, 500).unwrap();
+        assert_eq!(sponsor.amount, 300);
+        assert_eq!(main_state.total_sponsor_deposit, 300);
+        assert_eq!(tree.total_twab(), 100);
+        assert_error(
+            record_withdrawal(&mut main_state, &mut sponsor, tree, 301, 500),
+            SolscatterError::WithdrawAmountExceedsDeposit,
+        );
+
+        // sponsors are frozen with everyone else while drawing
+        main_state.drawing_in_progress = true;
+        assert_error(
+            record_deposit(&mut main_state, &mut sponsor, tree, 1, 500),
+            SolscatterError::DrawingInProgress,
+        );
+    }
+
+    #[test]
+    fn an_emptied_deposit_keeps_its_twab_until_the_round_ends() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        let mut main_state = new_main_state();
+        let mut player = new_user_deposit(1);
+
+        record_deposit(&mut main_state, &mut player, tree, 100, 0).unwrap();
+        record_withdrawal(&mut main_state, &mut player, tree, 100, 500).unwrap();
+
+        // still drawn this round, so it cannot become a sponsor yet
+        assert_eq!((player.amount, player.twab), (0, 50));
+        assert!(player.has_twab(main_state.current_round));
+        assert_eq!(tree.total_twab(), 50);
+
+        main_state.start_next_round(1_000);
+        assert!(!player.has_twab(main_state.current_round));
+    }
 }
@@ -1,60 +1,82 @@
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{TokenAccount, Mint, Token};
 use crate::state::user_deposit::UserDeposit;
-use crate::state::main_state::MainState;
+use crate::state::user_deposit_index::UserDepositIndex;
+use crate::state::main_state::{MainState, PAUSE_DEPOSIT};
+use crate::state::deposit_tree::DEPOSIT_TREE_CAPACITY;
+use crate::error::SolscatterError;
+use crate::{MAIN_STATE_SEED, USER_SEED};
 
+/// Allocates the depositor's slot on their first call, later calls reuse it and do nothing.
+/// Token accounts are left to the depositor, since a SOL pool needs none.
 #[derive(Accounts)]
 pub struct DepositInitialize<'info> {
     #[account(
-        init,
+        init_if_needed,
+        payer = depositor,
+        seeds = [USER_SEED, main_state.key().as_ref(), depositor.key().as_ref()],
+        bump,
+        space = UserDepositIndex::LEN,
+    )]
+    pub user_deposit_index: Account<'info, UserDepositIndex>,
+    #[account(
+        init_if_needed,
         payer = depositor,
-        seeds = [(main_state.current_slot + 1).to_le_bytes().as_ref()],
+        seeds = [
+            main_state.key().as_ref(),
+            user_deposit_index.slot_or(main_state.current_slot + 1).to_le_bytes().as_ref(),
+        ],
         bump,
         space = UserDeposit::LEN,
     )]
     pub user_deposit: Account<'info, UserDeposit>,
     #[account(
         mut,
-        seeds = [b"main_state"],
-        bump,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_DEPOSIT) @ SolscatterError::Paused,
     )]
     pub main_state: Account<'info, MainState>,
     #[account(mut)]
     pub depositor: Signer<'info>,
-
-    pub yi_underlying_mint: Box<Account<'info, Mint>>,
-    pub yi_mint: Box<Account<'info, Mint>>,
-    #[account(
-        init_if_needed,
-        payer = depositor,
-        associated_token::mint = yi_underlying_mint,
-        associated_token::authority = depositor,
-    )]
-    pub sol_ust_token_account: Account<'info, TokenAccount>,
-    #[account(
-        init_if_needed,
-        payer = depositor,
-        associated_token::mint = yi_mint,
-        associated_token::authority = depositor,
-    )]
-    pub yi_sol_ust_token_account: Account<'info, TokenAccount>,
     pub rent: Sysvar<'info, Rent>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn handler(ctx: Context<DepositInitialize>) -> Result<()> {
+    if ctx.accounts.user_deposit_index.is_initialized() {
+        return Ok(());
+    }
+
+    let user_deposit_index = &mut ctx.accounts.user_deposit_index;
     let user_deposit = &mut ctx.accounts.user_deposit;
     let main_state = &mut ctx.accounts.main_state;
     let depositor = &ctx.accounts.depositor;
 
+    if main_state.current_slot >= DEPOSIT_TREE_CAPACITY as u64 {
+        return Err(error!(SolscatterError::DepositTreeFull));
+    }
+
+    user_deposit.pool = main_state.key();
     user_deposit.slot = main_state.current_slot + 1;
     user_deposit.amount = 0;
-    user_deposit.owner = depositor.key().clone();
+    user_deposit.owner = depositor.key();
     user_deposit.latest_deposit_timestamp = None;
+    user_deposit.bump = *ctx.bumps.get("user_deposit").unwrap();
+    user_deposit.twab = 0;
+    user_deposit.twab_round = main_state.current_round;
+    user_deposit.last_won_round = 0;
+    user_deposit.auto_compound = false;
+    user_deposit.is_sponsor = false;
+
+    user_deposit_index.pool = main_state.key();
+    user_deposit_index.owner = depositor.key();
+    user_deposit_index.slot = user_deposit.slot;
+    user_deposit_index.bump = *ctx.bumps.get("user_deposit_index").unwrap();
 
-    main_state.current_slot = main_state.current_slot + 1;
+    main_state.current_slot += 1;
     Ok(())
 }
@@ -1,22 +1,38 @@
 use anchor_lang::prelude::*;
+use crate::error::SolscatterError;
+use crate::events::{CompoundedPrizeEvent, DrawingFinishedEvent};
 use crate::state::{
-    main_state::MainState,
+    main_state::{MainState, PAUSE_DRAWING},
     user_deposit::UserDeposit,
-    drawing_result::{DrawingResult, DrawingState},
+    drawing_result::{DrawingResult, DrawingState, WinnerPolicy, MAX_REDRAWS},
+    drawing_winners_page::DrawingWinnersPage,
+    DepositTree,
 };
+use crate::MAIN_STATE_SEED;
 
+/// Rewards are drawn strictly in reward order, so `winners_page` must hold the next undrawn reward.
+///
+/// Remaining accounts: the [UserDeposit] of winning slots in `winners_page`, looked up off-chain with the same tree walk.
+/// They must be writable and unique, since each winner is marked with the round it won.
+/// The call stops at the first winner whose [UserDeposit] is not passed, the rest are left for a later call.
 #[derive(Accounts)]
 pub struct Drawing<'info> {
     #[account(
         mut,
-        seeds = [b"main_state"],
-        bump
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_DRAWING) @ SolscatterError::Paused,
     )]
     pub main_state: Account<'info, MainState>,
     #[account(
         mut,
         seeds = [
             b"drawing_result",
+            main_state.key().as_ref(),
             main_state.current_round.to_le_bytes().as_ref(),
         ],
         bump,
@@ -24,50 +40,182 @@ pub struct Drawing<'info> {
     )]
     pub drawing_result: Account<'info, DrawingResult>,
     #[account(
-        seeds = [(drawing_result.last_processed_slot + 1).to_le_bytes().as_ref()],
-        bump,
+        mut,
+        constraint = winners_page.load()?.drawing_result == drawing_result.key() @ SolscatterError::InvalidWinnersPage,
+        constraint = winners_page.load()?.holds_reward(drawing_result.drawn_rewards as usize) @ SolscatterError::DrawingOutOfOrder,
+    )]
+    pub winners_page: AccountLoader<'info, DrawingWinnersPage>,
+    #[account(
+        mut,
+        address = main_state.deposit_tree,
     )]
-    pub user_deposit: Account<'info, UserDeposit>,
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<Drawing>) -> Result<()> {
-    let drawing_result = &mut ctx.accounts.drawing_result;
-    let random_numbers = drawing_result.random_numbers.clone();
-    let winners = drawing_result.winners.clone();
+fn load_user_deposit<'info>(pool: Pubkey, account_info: &AccountInfo<'info>) -> Result<Account<'info, UserDeposit>> {
+    let user_deposit: Account<UserDeposit> = Account::try_from(account_info)?;
+    let expected_address = Pubkey::create_program_address(
+        &[pool.as_ref(), user_deposit.slot.to_le_bytes().as_ref(), &[user_deposit.bump]],
+        &crate::ID,
+    ).map_err(|_| SolscatterError::InvalidUserDepositAccount)?;
+
+    if account_info.key() != expected_address {
+        return Err(error!(SolscatterError::InvalidUserDepositAccount));
+    }
 
+    Ok(user_deposit)
+}
+
+/// Adds a drawn prize to the winner's deposit. It carries no weight in the round being drawn, only from the next one.
+pub(crate) fn compound_prize(
+    main_state: &mut MainState,
+    user_deposit: &mut UserDeposit,
+    deposit_tree: &mut DepositTree,
+    amount: u64,
+) -> Result<()> {
+    user_deposit.sync_twab(main_state.current_round);
+    user_deposit.amount += amount;
+    main_state.total_deposit += amount;
+    main_state.unclaimed_prize = main_state.unclaimed_prize.saturating_sub(amount);
+    deposit_tree.add(user_deposit.slot, amount, 0)
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Drawing<'info>>) -> Result<()> {
+    // every copy of an account is written back on exit, an untouched duplicate would undo the winner's update
+    let mut keys = ctx.remaining_accounts.iter().map(|account_info| account_info.key()).collect::<Vec<_>>();
+    keys.sort();
+    keys.dedup();
+    if keys.len() != ctx.remaining_accounts.len() {
+        return Err(error!(SolscatterError::DuplicateUserDeposit));
+    }
+
+    let mut user_deposits = ctx.remaining_accounts
+        .iter()
+        .map(|account_info| load_user_deposit(ctx.accounts.main_state.key(), account_info))
+        .collect::<Result<Vec<_>>>()?;
+
+    let deposit_tree = &mut ctx.accounts.deposit_tree.load_mut()?;
+    let winners_page = &mut ctx.accounts.winners_page.load_mut()?;
+    let drawing_result = &mut ctx.accounts.drawing_result;
     let main_state = &mut ctx.accounts.main_state;
-    let user_deposit = &ctx.accounts.user_deposit;
-
-    let mut index: usize = 0;
-    let mut winner_count: u8 = 0;
-    for random_number in random_numbers.into_iter() {
-        match winners[index] {
-            Some(_) => {
-                winner_count += 1;
-            },
-            None => {
-                if random_number < user_deposit.amount {
-                    drawing_result.winners[index] = Some(user_deposit.owner);
-                    winner_count += 1;
-                }
-
-                drawing_result.random_numbers[index] -= user_deposit.amount;
-            },
+
+    let round = drawing_result.round;
+    let first_reward_index = winners_page.first_reward_index();
+    let number_of_entries = winners_page.number_of_entries as usize;
+    let first_undrawn = drawing_result.drawn_rewards as usize - first_reward_index;
+    let mut drawn_rewards: u16 = 0;
+    let mut redraws: u16 = 0;
+    // a later reward is never drawn before an earlier one, or the caller could pick who gets redrawn under the Redraw policy
+    'entries: for (index, entry) in winners_page.entries[..number_of_entries].iter_mut().enumerate().skip(first_undrawn) {
+        loop {
+            let slot = deposit_tree.find_slot(entry.random_number)?;
+            let user_deposit = match user_deposits.iter_mut().find(|user_deposit| user_deposit.slot == slot) {
+                Some(user_deposit) => user_deposit,
+                None => break 'entries,
+            };
+
+            // sponsors are never drawn, repeat winners are redrawn under the Redraw policy
+            let ineligible = user_deposit.is_sponsor
+                || (drawing_result.winner_policy == WinnerPolicy::Redraw && user_deposit.last_won_round == round);
+            if ineligible && entry.redraws < MAX_REDRAWS {
+                entry.redraws += 1;
+                entry.random_number = drawing_result.derive_random_number(
+                    (first_reward_index + index) as u16,
+                    entry.redraws,
+                );
+                redraws += 1;
+                continue;
+            }
+
+            if user_deposit.is_sponsor {
+                // out of redraws, left for an abort
+                break 'entries;
+            }
+
+            entry.winner = user_deposit.owner;
+            entry.drawn = 1;
+            user_deposit.last_won_round = round;
+            drawn_rewards += 1;
+
+            if user_deposit.auto_compound {
+                // the prize is already staked in the vault, it only changes hands
+                entry.claimed = 1;
+                compound_prize(main_state, user_deposit, deposit_tree, entry.amount)?;
+                drawing_result.compounded_amount += entry.amount;
+
+                emit!(CompoundedPrizeEvent {
+                    round,
+                    reward_index: (first_reward_index + index) as u16,
+                    winner: user_deposit.owner,
+                    amount: entry.amount,
+                });
+            }
+            break;
         }
+    }
 
-        index += 1;
+    if drawn_rewards == 0 && redraws == 0 {
+        return Err(error!(SolscatterError::MissingWinnerUserDeposit));
     }
 
-    if winner_count == drawing_result.number_of_rewards {
-        // all winners are found
-        drawing_result.finished_timestamp = Some(ctx.accounts.clock.unix_timestamp);
-        drawing_result.state = DrawingState::Finished;
+    for user_deposit in user_deposits.iter() {
+        user_deposit.exit(&crate::ID)?;
+    }
 
-        main_state.current_round = main_state.current_round + 1;
+    drawing_result.drawn_rewards += drawn_rewards;
+    if drawing_result.drawn_rewards < drawing_result.number_of_rewards {
         return Ok(());
-    } 
-    
-    drawing_result.last_processed_slot = drawing_result.last_processed_slot + 1;
-    return Ok(());
+    }
+
+    // all winners are found
+    drawing_result.finished_timestamp = Some(ctx.accounts.clock.unix_timestamp);
+    drawing_result.state = DrawingState::Finished;
+
+    main_state.drawing_in_progress = false;
+    main_state.start_next_round(ctx.accounts.clock.unix_timestamp);
+    deposit_tree.reset_twabs();
+
+    emit!(DrawingFinishedEvent {
+        round: drawing_result.round,
+        aborted: false,
+        rollover_amount: main_state.rollover_amount,
+        finished_timestamp: ctx.accounts.clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::record_deposit;
+    use crate::state::fixtures::{as_tree, new_main_state, new_tree, new_user_deposit};
+
+    #[test]
+    fn compounded_prizes_count_as_deposits_but_not_twab() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        let mut main_state = new_main_state();
+        let mut winner = new_user_deposit(1);
+        let mut other = new_user_deposit(2);
+        record_deposit(&mut main_state, &mut winner, tree, 100, 0).unwrap();
+        record_deposit(&mut main_state, &mut other, tree, 100, 500).unwrap();
+        main_state.unclaimed_prize = 40;
+        main_state.drawing_in_progress = true;
+
+        compound_prize(&mut main_state, &mut winner, tree, 40).unwrap();
+
+        assert_eq!(winner.amount, 140);
+        assert_eq!(winner.twab, 100);
+        assert_eq!(main_state.total_deposit, 240);
+        assert_eq!(main_state.unclaimed_prize, 0);
+        assert_eq!(tree.total_twab(), 150);
+        assert_eq!(tree.find_slot(99).unwrap(), 1);
+        assert_eq!(tree.find_slot(100).unwrap(), 2);
+
+        // the prize weighs in from the next round
+        tree.reset_twabs();
+        assert_eq!(tree.total_twab(), 240);
+        assert_eq!(tree.find_slot(139).unwrap(), 1);
+    }
 }
@@ -0,0 +1,160 @@
+use crate::{
+    events::ProtocolFeeEvent,
+    state::{main_state::MainState, MAX_BPS},
+    yield_source::{PoolVault, YieldSourceAdapter},
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Remaining accounts: the accounts of the pool's [YieldSource](crate::yield_source::YieldSource) adapter.
+#[derive(Accounts)]
+pub struct Harvest<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+    /// Program-owned vault holding every deposit.
+    #[account(
+        mut,
+        address = main_state.vault,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    /// Program-owned treasury receiving the protocol fee.
+    #[account(
+        mut,
+        address = main_state.treasury,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Harvest<'info> {
+    fn into_transfer_cpi_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.vault.to_account_info(),
+                to: self.treasury.to_account_info(),
+                authority: self.main_state.to_account_info(),
+            },
+        )
+    }
+
+    fn transfer_fee(&self, yield_source: &dyn YieldSourceAdapter<'info>, fee_amount: u64) -> Result<()> {
+        let vault_amount = yield_source.to_vault_amount(fee_amount)?;
+        yield_source.unstake_to_vault(vault_amount)?;
+        let signer_seeds = self.main_state.signer_seeds();
+        token::transfer(
+            self.into_transfer_cpi_context().with_signer(&[&signer_seeds]),
+            vault_amount,
+        )
+    }
+
+    pub fn harvest(&mut self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let yield_source = PoolVault::new(&self.main_state, &self.vault, self.token_program.to_account_info())
+            .load_yield_source(remaining_accounts)?;
+        let vault_value = yield_source.current_value()?;
+        let (prize_pool, fee_amount) = calculate_harvest(&self.main_state, vault_value);
+        if fee_amount > 0 {
+            self.transfer_fee(yield_source.as_ref(), fee_amount)?;
+        }
+        drop(yield_source);
+
+        let main_state = &mut self.main_state;
+        record_harvest(main_state, prize_pool, fee_amount);
+        msg!("vault value: {}, prize pool: {}", vault_value, main_state.prize_pool);
+
+        if fee_amount > 0 {
+            emit!(ProtocolFeeEvent {
+                round: main_state.current_round,
+                fee_amount,
+                round_fee_amount: main_state.round_fee_amount,
+                harvested_timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Returns the prize pool of a vault worth `vault_value` and the fee taken out of it.
+/// Whatever the vault holds beyond deposits and unclaimed prizes is prize, only yield accrued since the last harvest is charged.
+pub(crate) fn calculate_harvest(main_state: &MainState, vault_value: u64) -> (u64, u64) {
+    let prize_pool = vault_value
+        .saturating_sub(main_state.total_deposit)
+        .saturating_sub(main_state.total_sponsor_deposit)
+        .saturating_sub(main_state.unclaimed_prize);
+
+    let new_yield = prize_pool.saturating_sub(main_state.prize_pool);
+    let fee_amount = (new_yield as u128 * main_state.fee_bps as u128 / MAX_BPS as u128) as u64;
+    (prize_pool - fee_amount, fee_amount)
+}
+
+pub(crate) fn record_harvest(main_state: &mut MainState, prize_pool: u64, fee_amount: u64) {
+    main_state.prize_pool = prize_pool;
+    main_state.rollover_amount = std::cmp::min(main_state.rollover_amount, prize_pool);
+    main_state.round_fee_amount += fee_amount;
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Harvest<'info>>) -> Result<()> {
+    ctx.accounts.harvest(ctx.remaining_accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::new_main_state;
+
+    fn harvest(main_state: &mut MainState, vault_value: u64) -> u64 {
+        let (prize_pool, fee_amount) = calculate_harvest(main_state, vault_value);
+        record_harvest(main_state, prize_pool, fee_amount);
+        fee_amount
+    }
+
+    #[test]
+    fn fees_are_taken_from_new_yield_only() {
+        let mut main_state = new_main_state();
+        main_state.total_deposit = 1_000;
+        main_state.total_sponsor_deposit = 200;
+        main_state.unclaimed_prize = 50;
+        main_state.prize_pool = 30;
+        main_state.rollover_amount = 30;
+        main_state.fee_bps = 1_000;
+
+        // 130 above what is owed, 100 of it new since the last harvest
+        assert_eq!(harvest(&mut main_state, 1_380), 10);
+        assert_eq!(main_state.prize_pool, 120);
+        assert_eq!(main_state.rollover_amount, 30);
+        assert_eq!(main_state.round_fee_amount, 10);
+
+        // the fee has left the vault, nothing new to charge
+        assert_eq!(harvest(&mut main_state, 1_370), 0);
+        assert_eq!(main_state.prize_pool, 120);
+        assert_eq!(main_state.round_fee_amount, 10);
+
+        assert_eq!(harvest(&mut main_state, 1_390), 2);
+        assert_eq!(main_state.prize_pool, 138);
+        assert_eq!(main_state.round_fee_amount, 12);
+    }
+
+    #[test]
+    fn a_vault_loss_shrinks_the_prize_pool_and_rollover() {
+        let mut main_state = new_main_state();
+        main_state.total_deposit = 1_000;
+        main_state.prize_pool = 50;
+        main_state.rollover_amount = 50;
+        main_state.fee_bps = 1_000;
+
+        assert_eq!(harvest(&mut main_state, 1_020), 0);
+        assert_eq!((main_state.prize_pool, main_state.rollover_amount), (20, 20));
+
+        assert_eq!(harvest(&mut main_state, 990), 0);
+        assert_eq!((main_state.prize_pool, main_state.rollover_amount), (0, 0));
+    }
+}
@@ -1,18 +1,28 @@
 use crate::{
+    MAIN_STATE_SEED,
     STATE_SEED,
+    VAULT_SEED,
+    TREASURY_SEED,
     error::SolscatterError,
-    state::{main_state::MainState, VrfClientState},
+    yield_source::YieldSource,
+    state::{main_state::{MainState, DEFAULT_CLAIM_WINDOW, DEFAULT_ROUND_DURATION}, DepositTree, PrizeTier, VrfClientState, WinnerPolicy, MAX_BPS},
 };
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use switchboard_v2::VrfAccountData;
 
 #[derive(Accounts)]
+#[instruction(params: InitializeParams)]
 pub struct Initialize<'info> {
     #[account(
         init,
         payer = signer,
         space = MainState::LEN,
-        seeds = [b"main_state"],
+        seeds = [
+            MAIN_STATE_SEED,
+            underlying_mint.key().as_ref(),
+            params.pool_id.to_le_bytes().as_ref(),
+        ],
         bump,
     )]
     pub main_state: Account<'info, MainState>,
@@ -22,23 +32,65 @@ pub struct Initialize<'info> {
         seeds = [
             STATE_SEED,
             vrf_account_info.key().as_ref(),
-            signer.to_account_info().key().as_ref(),
+            main_state.key().as_ref(),
         ],
         bump,
     )]
     pub vrf_client_state: AccountLoader<'info, VrfClientState>,
     /// CHECK: This is our VrfAccountData
     pub vrf_account_info: AccountInfo<'info>,
+    /// Mint deposited into the pool, e.g. solUST
+    pub underlying_mint: Box<Account<'info, Mint>>,
+    /// Mint held by the vault: yi-solUST for [YieldSource::Yi] and [YieldSource::Quarry], the underlying mint for [YieldSource::NoYield]
+    pub vault_mint: Box<Account<'info, Mint>>,
+    /// Program-owned vault holding every deposit
+    #[account(
+        init,
+        payer = signer,
+        seeds = [VAULT_SEED, main_state.key().as_ref()],
+        bump,
+        token::mint = vault_mint,
+        token::authority = main_state,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    /// Program-owned treasury collecting the protocol fee in the vault mint
+    #[account(
+        init,
+        payer = signer,
+        seeds = [TREASURY_SEED, main_state.key().as_ref()],
+        bump,
+        token::mint = vault_mint,
+        token::authority = main_state,
+    )]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+    /// Pre-allocated by the client with [DepositTree::LEN] bytes, too large to be created by the program
+    #[account(zero)]
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
     #[account(mut)]
     pub signer: Signer<'info>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeParams {
+    /// Tells apart pools sharing an underlying mint.
+    pub pool_id: u64,
+    pub yield_source: YieldSource,
+    /// Defaults to [DEFAULT_ROUND_DURATION], also sets the length of the first round.
+    pub round_duration: Option<i64>,
+}
+
 impl<'info> Initialize<'info> {
-    pub fn validate(&self, ctx: &Context<Self>) -> Result<()> {
+    pub fn validate(&self, ctx: &Context<Self>, params: &InitializeParams) -> Result<()> {
         let vrf_account_info = &ctx.accounts.vrf_account_info;
         VrfAccountData::new(vrf_account_info)
             .map_err(|_| SolscatterError::InvalidSwitchboardVrfAccount)?;
+
+        if params.round_duration.map_or(false, |round_duration| round_duration <= 0) {
+            return Err(error!(SolscatterError::InvalidRoundDuration));
+        }
         Ok(())
     }
 
@@ -46,26 +98,60 @@ impl<'info> Initialize<'info> {
         let state = &mut self.vrf_client_state.load_init()?;
         state.max_result = u64::MAX;
         state.vrf = self.vrf_account_info.key().clone();
-        state.authority = self.signer.to_account_info().key().clone(); 
+        state.authority = self.main_state.key();
+        state.pool = self.main_state.key();
         Ok(())
     }
 
-    fn initialize_main_state(&mut self) -> Result<()> {
+    fn initialize_main_state(&mut self, params: &InitializeParams, bump: u8) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round_duration = params.round_duration.unwrap_or(DEFAULT_ROUND_DURATION);
         let main_state = &mut self.main_state;
+        main_state.underlying_mint = self.underlying_mint.key();
+        main_state.pool_id = params.pool_id;
+        main_state.yield_source = params.yield_source;
+        main_state.admin = self.signer.key();
+        main_state.pending_admin = None;
+        main_state.number_of_rewards = 1;
+        main_state.prize_tiers = vec![PrizeTier {
+            number_of_rewards: 1,
+            share_bps: MAX_BPS as u16,
+        }];
+        main_state.winner_policy = WinnerPolicy::AllowDuplicates;
+        main_state.pause_flags = 0;
+        main_state.round_duration = round_duration;
+        main_state.deposit_cutoff = 0;
+        main_state.claim_window = DEFAULT_CLAIM_WINDOW;
+        main_state.round_start_timestamp = now;
+        main_state.round_end_timestamp = now + round_duration;
+        main_state.drawing_in_progress = false;
         main_state.current_slot = 0;
         main_state.current_round = 1;
         main_state.total_deposit = 0;
+        main_state.total_sponsor_deposit = 0;
         main_state.vrf_account_pubkey = self.vrf_account_info.key();
+        main_state.vault = self.vault.key();
+        main_state.treasury = self.treasury.key();
+        main_state.miner = Pubkey::default();
+        main_state.fee_bps = 0;
+        main_state.round_fee_amount = 0;
+        main_state.bump = bump;
+        main_state.last_vrf_timestamp = 0;
+        main_state.prize_pool = 0;
+        main_state.unclaimed_prize = 0;
+        main_state.rollover_amount = 0;
+        main_state.deposit_tree = self.deposit_tree.key();
         Ok(())
     }
 
-    pub fn initialize(&mut self) -> Result<()> {
+    pub fn initialize(&mut self, params: InitializeParams, bump: u8) -> Result<()> {
         self.initialize_vrf()?;
-        self.initialize_main_state()?;
+        self.initialize_main_state(&params, bump)?;
         Ok(())
     }
 }
 
-pub fn handler(ctx: Context<Initialize>) -> Result<()> {
-    ctx.accounts.initialize()
+pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+    let bump = *ctx.bumps.get("main_state").unwrap();
+    ctx.accounts.initialize(params, bump)
 }
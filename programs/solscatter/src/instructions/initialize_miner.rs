@@ -0,0 +1,114 @@
+use crate::{
+    MAIN_STATE_SEED,
+    MINER_VAULT_SEED,
+    QUARRY_REWARDS_SEED,
+    error::SolscatterError,
+    state::main_state::MainState,
+    yield_source::YieldSource,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use quarry_mine::{cpi::accounts::CreateMiner, program::QuarryMine, Quarry, Rewarder};
+
+/// Creates the quarry [quarry_mine::Miner] of a [YieldSource::Quarry] pool, owned by the pool.
+#[derive(Accounts)]
+pub struct InitializeMiner<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+        has_one = vault,
+        constraint = main_state.yield_source == YieldSource::Quarry @ SolscatterError::UnsupportedYieldSource,
+        constraint = main_state.miner == Pubkey::default() @ SolscatterError::MinerAlreadyInitialized,
+    )]
+    pub main_state: Box<Account<'info, MainState>>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: Created and validated by the quarry mine program
+    #[account(mut)]
+    pub miner: UncheckedAccount<'info>,
+    /// [Quarry] mining the vault mint
+    #[account(
+        mut,
+        constraint = quarry.rewarder_key == rewarder.key() @ SolscatterError::InvalidYieldSourceAccounts,
+        constraint = quarry.token_mint_key == vault.mint @ SolscatterError::InvalidYieldSourceAccounts,
+    )]
+    pub quarry: Box<Account<'info, Quarry>>,
+    pub rewarder: Box<Account<'info, Rewarder>>,
+    pub vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = vault.mint)]
+    pub vault_mint: Box<Account<'info, Mint>>,
+    /// Holds the mined vault tokens
+    #[account(
+        init,
+        payer = admin,
+        seeds = [MINER_VAULT_SEED, main_state.key().as_ref()],
+        bump,
+        token::mint = vault_mint,
+        token::authority = miner,
+    )]
+    pub miner_vault: Box<Account<'info, TokenAccount>>,
+    #[account(address = rewarder.rewards_token_mint)]
+    pub rewards_token_mint: Box<Account<'info, Mint>>,
+    /// Program-owned account receiving the mining rewards
+    #[account(
+        init,
+        payer = admin,
+        seeds = [QUARRY_REWARDS_SEED, main_state.key().as_ref()],
+        bump,
+        token::mint = rewards_token_mint,
+        token::authority = main_state,
+    )]
+    pub rewards_tokens: Box<Account<'info, TokenAccount>>,
+    pub quarry_mine_program: Program<'info, QuarryMine>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMiner<'info> {
+    fn into_create_miner_context(&self) -> CpiContext<'_, '_, '_, 'info, CreateMiner<'info>> {
+        CpiContext::new(
+            self.quarry_mine_program.to_account_info(),
+            CreateMiner {
+                authority: self.main_state.to_account_info(),
+                miner: self.miner.to_account_info(),
+                quarry: self.quarry.to_account_info(),
+                rewarder: self.rewarder.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                payer: self.admin.to_account_info(),
+                token_mint: self.vault_mint.to_account_info(),
+                miner_vault: self.miner_vault.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        )
+    }
+
+    fn create_miner(&self) -> Result<()> {
+        let (_, miner_bump) = Pubkey::find_program_address(
+            &[
+                b"Miner",
+                self.quarry.key().as_ref(),
+                self.main_state.key().as_ref(),
+            ],
+            &quarry_mine::id(),
+        );
+
+        let signer_seeds = self.main_state.signer_seeds();
+        quarry_mine::cpi::create_miner(
+            self.into_create_miner_context().with_signer(&[&signer_seeds]),
+            miner_bump,
+        )
+    }
+}
+
+pub fn handler(ctx: Context<InitializeMiner>) -> Result<()> {
+    ctx.accounts.create_miner()?;
+    ctx.accounts.main_state.miner = ctx.accounts.miner.key();
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::error::SolscatterError;
+use crate::state::{
+    drawing_result::{DrawingResult, DrawingState},
+    drawing_winners_page::DrawingWinnersPage,
+};
+
+/// Allocates the next winners page of a drawing. Pages are created in order.
+#[derive(Accounts)]
+pub struct InitializeWinnersPage<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"drawing_result",
+            drawing_result.pool.as_ref(),
+            drawing_result.round.to_le_bytes().as_ref(),
+        ],
+        bump,
+        constraint = drawing_result.state == DrawingState::Processing,
+        constraint = drawing_result.initialized_pages < drawing_result.number_of_pages @ SolscatterError::InvalidWinnersPage,
+    )]
+    pub drawing_result: Account<'info, DrawingResult>,
+    #[account(
+        init,
+        payer = payer,
+        space = DrawingWinnersPage::LEN,
+        seeds = [
+            b"drawing_winners",
+            drawing_result.key().as_ref(),
+            drawing_result.initialized_pages.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub winners_page: AccountLoader<'info, DrawingWinnersPage>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeWinnersPage>) -> Result<()> {
+    let drawing_result = &mut ctx.accounts.drawing_result;
+    let winners_page = &mut ctx.accounts.winners_page.load_init()?;
+    winners_page.initialize(drawing_result.key(), drawing_result, drawing_result.initialized_pages);
+    drawing_result.initialized_pages += 1;
+    Ok(())
+}
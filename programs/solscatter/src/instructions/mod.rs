@@ -3,15 +3,51 @@ pub mod callback_request_randomness;
 pub mod request_randomness;
 pub mod deposit_initialize;
 pub mod deposit;
+pub mod withdraw;
+pub mod deposit_sol;
+pub mod withdraw_sol;
+pub mod harvest;
+pub mod withdraw_treasury;
+pub mod claim_prize;
+pub mod sweep_unclaimed;
+pub mod update_config;
+pub mod transfer_admin;
+pub mod accept_admin;
+pub mod set_pause;
+pub mod set_auto_compound;
+pub mod set_sponsor;
 pub mod stake;
 pub mod start_drawing_phase;
+pub mod initialize_winners_page;
 pub mod drawing;
+pub mod abort_drawing;
+pub mod initialize_miner;
+pub mod claim_quarry_rewards;
+pub mod withdraw_quarry_rewards;
 
 pub use initialize::*;
 pub use callback_request_randomness::*;
 pub use request_randomness::*;
 pub use deposit_initialize::*;
 pub use deposit::*;
+pub use withdraw::*;
+pub use deposit_sol::*;
+pub use withdraw_sol::*;
+pub use harvest::*;
+pub use withdraw_treasury::*;
+pub use claim_prize::*;
+pub use sweep_unclaimed::*;
+pub use update_config::*;
+pub use transfer_admin::*;
+pub use accept_admin::*;
+pub use set_pause::*;
+pub use set_auto_compound::*;
+pub use set_sponsor::*;
 pub use stake::*;
 pub use start_drawing_phase::*;
-pub use drawing::*;
\ No newline at end of file
+pub use initialize_winners_page::*;
+pub use drawing::*;
+pub use abort_drawing::*;
+pub use initialize_miner::*;
+pub use claim_quarry_rewards::*;
+pub use withdraw_quarry_rewards::*;
\ No newline at end of file
@@ -1,9 +1,11 @@
-use crate::STATE_SEED;
-use crate::state::VrfClientState;
+use crate::{MAIN_STATE_SEED, STATE_SEED};
+use crate::error::SolscatterError;
+use crate::state::{main_state::MainState, VrfClientState};
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 pub use switchboard_v2::{VrfAccountData, VrfRequestRandomness};
 
+/// The VRF client state is seeded by the pool, so whoever is the pool's admin can request randomness.
 #[derive(Accounts)]
 #[instruction(params: RequestRandomnessParams)] // rpc parameters hint
 pub struct RequestRanmdomness<'info> {
@@ -12,15 +14,24 @@ pub struct RequestRanmdomness<'info> {
         seeds = [
             STATE_SEED, 
             vrf.key().as_ref(),
-            authority.key().as_ref(),
+            main_state.key().as_ref(),
         ],
         bump = params.client_state_bump,
         constraint = state.load()?.vrf == vrf.key()
     )]
     pub state: AccountLoader<'info, VrfClientState>,
-    #[account(signer)] // client authority needs to sign
-    /// CHECK: this is vrf related account
-    pub authority: AccountInfo<'info>,
+    #[account(
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+        constraint = main_state.vrf_account_pubkey == vrf.key() @ SolscatterError::InvalidSwitchboardVrfAccount,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub admin: Signer<'info>,
     /// CHECK: this is vrf related account
     pub switchboard_program: AccountInfo<'info>,
     #[account(mut)]
@@ -84,11 +95,11 @@ impl RequestRanmdomness<'_> {
         };
 
         let vrf_key = ctx.accounts.vrf.key.clone();
-        let authority_key = ctx.accounts.authority.key.clone();
+        let main_state_key = ctx.accounts.main_state.key();
         let state_seeds: &[&[&[u8]]] = &[&[
             &STATE_SEED,
             vrf_key.as_ref(),
-            authority_key.as_ref(),
+            main_state_key.as_ref(),
             &[params.client_state_bump],
         ]];
         msg!("requesting randomness");
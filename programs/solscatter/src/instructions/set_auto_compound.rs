@@ -0,0 +1,18 @@
+use crate::state::user_deposit::UserDeposit;
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetAutoCompound<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    pub owner: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetAutoCompound>, auto_compound: bool) -> Result<()> {
+    let user_deposit = &mut ctx.accounts.user_deposit;
+    user_deposit.auto_compound = auto_compound;
+    Ok(())
+}
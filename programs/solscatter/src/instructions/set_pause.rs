@@ -0,0 +1,30 @@
+use crate::{
+    state::main_state::MainState,
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub admin: Signer<'info>,
+}
+
+/// `pause_flags` is a bitfield of `PAUSE_DEPOSIT`, `PAUSE_WITHDRAW`, `PAUSE_DRAWING` and `PAUSE_CLAIM`.
+/// Withdrawals stay open unless `PAUSE_WITHDRAW` is set explicitly.
+pub fn handler(ctx: Context<SetPause>, pause_flags: u8) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.pause_flags = pause_flags;
+    msg!("pause flags: {:#06b}", pause_flags);
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use crate::{
+    error::SolscatterError,
+    state::{main_state::MainState, user_deposit::UserDeposit},
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct SetSponsor<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = user_deposit.pool == main_state.key() @ SolscatterError::InvalidUserDepositAccount,
+        constraint = user_deposit.amount == 0 @ SolscatterError::DepositNotEmpty,
+        constraint = !user_deposit.has_twab(main_state.current_round) @ SolscatterError::DepositNotEmpty,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub owner: Signer<'info>,
+}
+
+/// Only an empty deposit with no TWAB left in the current round can switch between sponsor and player,
+/// so its balance is never half in and half out of the drawing.
+pub fn handler(ctx: Context<SetSponsor>, is_sponsor: bool) -> Result<()> {
+    let user_deposit = &mut ctx.accounts.user_deposit;
+    user_deposit.is_sponsor = is_sponsor;
+    Ok(())
+}
@@ -1,54 +1,131 @@
 use anchor_lang::prelude::*;
-use crate::state::{drawing_result::{DrawingResult, DrawingState}, main_state::MainState};
+use crate::state::{drawing_result::{DrawingResult, DrawingState}, main_state::{MainState, PAUSE_DRAWING}, VrfClientState, DepositTree};
 use crate::error::SolscatterError;
+use crate::events::{DrawingFinishedEvent, DrawingStartedEvent};
+use crate::MAIN_STATE_SEED;
 
 #[derive(Accounts)]
-#[instruction(number_of_rewards: u8, random_numbers: Vec<u64>)]
 pub struct StartDrawingPhase<'info> {
     #[account(
         init,
-        payer = signer,
-        space = DrawingResult::space(number_of_rewards)?,
+        payer = admin,
+        space = DrawingResult::LEN,
         seeds = [
-            b"drawing_result", 
+            b"drawing_result",
+            main_state.key().as_ref(),
             main_state.current_round.to_le_bytes().as_ref(),
         ],
         bump,
     )]
     pub drawing_result: Account<'info, DrawingResult>,
     #[account(
-        seeds = [b"main_state"],
-        bump
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+        constraint = !main_state.is_paused(PAUSE_DRAWING) @ SolscatterError::Paused,
     )]
     pub main_state: Account<'info, MainState>,
+    #[account(
+        constraint = vrf_client_state.load()?.vrf == main_state.vrf_account_pubkey,
+        constraint = vrf_client_state.load()?.pool == main_state.key(),
+    )]
+    pub vrf_client_state: AccountLoader<'info, VrfClientState>,
+    #[account(
+        mut,
+        address = main_state.deposit_tree,
+    )]
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
     #[account(mut)]
-    pub signer: Signer<'info>,
+    pub admin: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<StartDrawingPhase>, number_of_rewards: u8, random_numbers: Vec<u64>) -> Result<()> {
-    if number_of_rewards <= 0 {
-        return Err(error!(SolscatterError::NumberOfRewardsMustMoreThanZero));
+pub fn handler(ctx: Context<StartDrawingPhase>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let main_state = &mut ctx.accounts.main_state;
+    let number_of_rewards = main_state.number_of_rewards;
+    if !main_state.is_round_ended(now) {
+        return Err(error!(SolscatterError::RoundNotEnded));
     }
 
-    if random_numbers.len() != number_of_rewards as usize {
-        return Err(error!(SolscatterError::NumberOfRandomNumbersNotMatchWithNumberOfRewards));
-    }
+    let deposit_tree = &mut ctx.accounts.deposit_tree.load_mut()?;
+    let total_twab = deposit_tree.total_twab();
 
-    let main_state = &ctx.accounts.main_state;
     let drawing_result = &mut ctx.accounts.drawing_result;
+    drawing_result.pool = main_state.key();
     drawing_result.round = main_state.current_round;
+    drawing_result.round_start_timestamp = main_state.round_start_timestamp;
+    drawing_result.round_end_timestamp = main_state.round_end_timestamp;
+    drawing_result.winner_policy = main_state.winner_policy;
+    drawing_result.prize_tiers = main_state.prize_tiers.clone();
+    drawing_result.total_twab = total_twab;
+    drawing_result.rollover_amount = main_state.rollover_amount;
+    drawing_result.compounded_amount = 0;
+    drawing_result.fee_amount = main_state.round_fee_amount;
+    main_state.round_fee_amount = 0;
+    drawing_result.claim_window = main_state.claim_window;
+    drawing_result.expired_amount = 0;
+
+    if total_twab == 0 {
+        // nobody is eligible, so the whole pool rolls into the next round
+        drawing_result.state = DrawingState::Aborted;
+        drawing_result.finished_timestamp = Some(now);
+        main_state.rollover_amount = main_state.prize_pool;
+        main_state.start_next_round(now);
+        deposit_tree.reset_twabs();
+
+        emit!(DrawingFinishedEvent {
+            round: drawing_result.round,
+            aborted: true,
+            rollover_amount: main_state.rollover_amount,
+            finished_timestamp: now,
+        });
+        return Ok(());
+    }
+
+    // the seed must come after the round's TWABs are frozen, or depositors could read it and move onto the winning ranges
+    let vrf_client_state = ctx.accounts.vrf_client_state.load()?;
+    if vrf_client_state.result_buffer == [0u8; 32]
+        || vrf_client_state.last_timestamp <= main_state.last_vrf_timestamp
+        || !vrf_client_state.has_round_result(main_state.round_end_timestamp)
+    {
+        return Err(error!(SolscatterError::VrfResultNotReady));
+    }
+    main_state.last_vrf_timestamp = vrf_client_state.last_timestamp;
+
     drawing_result.state = DrawingState::Processing;
     drawing_result.number_of_rewards = number_of_rewards;
-    drawing_result.winners = vec!(); 
-    drawing_result.random_numbers = random_numbers;
-    drawing_result.total_deposit = main_state.total_deposit;
-    drawing_result.last_processed_slot = 0;
+    drawing_result.number_of_pages = DrawingResult::number_of_pages(number_of_rewards);
+    drawing_result.initialized_pages = 0;
+    drawing_result.drawn_rewards = 0;
+    drawing_result.random_seed = vrf_client_state.result_buffer;
     drawing_result.finished_timestamp = None;
 
-    for _ in 0..number_of_rewards {
-        drawing_result.winners.push(None);
-    }
+    drawing_result.tier_payouts = main_state.prize_tiers
+        .iter()
+        .map(|prize_tier| prize_tier.calculate_payout(main_state.prize_pool))
+        .collect();
+
+    let prize_amount: u64 = drawing_result.rewards().map(|(_, payout)| payout).sum();
+    drawing_result.prize_amount = prize_amount;
+
+    // whatever the tiers do not hand out rolls into the next round
+    main_state.drawing_in_progress = true;
+    main_state.unclaimed_prize += prize_amount;
+    main_state.prize_pool -= prize_amount;
+    main_state.rollover_amount = main_state.prize_pool;
+
+    emit!(DrawingStartedEvent {
+        round: drawing_result.round,
+        prize_amount,
+        rollover_amount: drawing_result.rollover_amount,
+        started_timestamp: now,
+    });
 
     Ok(())
-}
\ No newline at end of file
+}
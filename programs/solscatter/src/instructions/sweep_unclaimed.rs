@@ -0,0 +1,61 @@
+use crate::{
+    error::SolscatterError,
+    state::{
+        drawing_result::{DrawingResult, DrawingState},
+        drawing_winners_page::DrawingWinnersPage,
+        main_state::{MainState, PAUSE_CLAIM},
+    },
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+
+/// Permissionless crank returning the prizes of a page left unclaimed past the claim window to the prize pool.
+/// Paused along with claims, so winners do not lose prizes they cannot claim.
+#[derive(Accounts)]
+pub struct SweepUnclaimed<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_CLAIM) @ SolscatterError::Paused,
+    )]
+    pub main_state: Account<'info, MainState>,
+    #[account(
+        mut,
+        seeds = [
+            b"drawing_result",
+            main_state.key().as_ref(),
+            drawing_result.round.to_le_bytes().as_ref(),
+        ],
+        bump,
+        constraint = drawing_result.state == DrawingState::Finished @ SolscatterError::DrawingNotFinished,
+    )]
+    pub drawing_result: Account<'info, DrawingResult>,
+    #[account(
+        mut,
+        constraint = winners_page.load()?.drawing_result == drawing_result.key() @ SolscatterError::InvalidWinnersPage,
+    )]
+    pub winners_page: AccountLoader<'info, DrawingWinnersPage>,
+}
+
+impl<'info> SweepUnclaimed<'info> {
+    pub fn sweep_unclaimed(&mut self) -> Result<()> {
+        if !self.drawing_result.is_claim_expired(Clock::get()?.unix_timestamp) {
+            return Err(error!(SolscatterError::ClaimWindowOpen));
+        }
+
+        let expired_amount = self.winners_page.load_mut()?.expire_unclaimed();
+        self.drawing_result.expired_amount += expired_amount;
+        self.main_state.roll_over(expired_amount);
+        msg!("round: {}, expired amount: {}", self.drawing_result.round, expired_amount);
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<SweepUnclaimed>) -> Result<()> {
+    ctx.accounts.sweep_unclaimed()
+}
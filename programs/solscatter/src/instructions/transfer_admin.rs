@@ -0,0 +1,27 @@
+use crate::{
+    state::main_state::MainState,
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    let main_state = &mut ctx.accounts.main_state;
+    main_state.pending_admin = Some(new_admin);
+    Ok(())
+}
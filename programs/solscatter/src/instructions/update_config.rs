@@ -0,0 +1,90 @@
+use crate::{
+    error::SolscatterError,
+    state::{main_state::MainState, PrizeTier, WinnerPolicy, MAX_BPS},
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub admin: Signer<'info>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateConfigParams {
+    pub prize_tiers: Option<Vec<PrizeTier>>,
+    /// Takes effect from the next drawing.
+    pub winner_policy: Option<WinnerPolicy>,
+    /// Takes effect from the next round.
+    pub round_duration: Option<i64>,
+    pub deposit_cutoff: Option<i64>,
+    /// Seconds winners have to claim once a drawing finishes. Takes effect from the next drawing.
+    pub claim_window: Option<i64>,
+    pub fee_bps: Option<u16>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn validate(&self, params: &UpdateConfigParams) -> Result<()> {
+        if let Some(prize_tiers) = &params.prize_tiers {
+            PrizeTier::validate_tiers(prize_tiers)?;
+        }
+
+        let round_duration = params.round_duration.unwrap_or(self.main_state.round_duration);
+        let deposit_cutoff = params.deposit_cutoff.unwrap_or(self.main_state.deposit_cutoff);
+        if round_duration <= 0 || deposit_cutoff < 0 || deposit_cutoff >= round_duration {
+            return Err(error!(SolscatterError::InvalidRoundDuration));
+        }
+
+        if let Some(claim_window) = params.claim_window {
+            if claim_window <= 0 {
+                return Err(error!(SolscatterError::InvalidClaimWindow));
+            }
+        }
+
+        if let Some(fee_bps) = params.fee_bps {
+            if fee_bps as u64 > MAX_BPS {
+                return Err(error!(SolscatterError::InvalidFee));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn update_config(&mut self, params: UpdateConfigParams) -> Result<()> {
+        let main_state = &mut self.main_state;
+        if let Some(prize_tiers) = params.prize_tiers {
+            main_state.number_of_rewards = PrizeTier::validate_tiers(&prize_tiers)?;
+            main_state.prize_tiers = prize_tiers;
+        }
+        if let Some(winner_policy) = params.winner_policy {
+            main_state.winner_policy = winner_policy;
+        }
+        if let Some(round_duration) = params.round_duration {
+            main_state.round_duration = round_duration;
+        }
+        if let Some(deposit_cutoff) = params.deposit_cutoff {
+            main_state.deposit_cutoff = deposit_cutoff;
+        }
+        if let Some(claim_window) = params.claim_window {
+            main_state.claim_window = claim_window;
+        }
+        if let Some(fee_bps) = params.fee_bps {
+            main_state.fee_bps = fee_bps;
+        }
+        Ok(())
+    }
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+    ctx.accounts.update_config(params)
+}
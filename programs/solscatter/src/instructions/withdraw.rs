@@ -0,0 +1,114 @@
+use crate::{
+    error::SolscatterError,
+    state::{main_state::{MainState, PAUSE_WITHDRAW}, user_deposit::UserDeposit, DepositTree},
+    yield_source::PoolVault,
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Remaining accounts: the accounts of the pool's [YieldSource](crate::yield_source::YieldSource) adapter.
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = user_deposit.pool == main_state.key() @ SolscatterError::InvalidUserDepositAccount,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_WITHDRAW) @ SolscatterError::Paused,
+    )]
+    pub main_state: Account<'info, MainState>,
+    #[account(
+        mut,
+        address = main_state.deposit_tree,
+    )]
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
+    pub owner: Signer<'info>,
+
+    /// Program-owned vault paying out the withdrawal.
+    #[account(
+        mut,
+        address = main_state.vault,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount] receiving the withdrawal in the pool's underlying mint.
+    #[account(
+        mut,
+        constraint = destination_tokens.owner == owner.key(),
+    )]
+    pub destination_tokens: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawParams {
+    pub amount: u64,
+}
+
+impl<'info> Withdraw<'info> {
+    fn unstake(&self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let yield_source = PoolVault::new(&self.main_state, &self.vault, self.token_program.to_account_info())
+            .load_yield_source(remaining_accounts)?;
+        yield_source.withdraw(&self.destination_tokens.to_account_info(), amount)
+    }
+
+    pub fn withdraw(&mut self, params: WithdrawParams, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        record_withdrawal(
+            &mut self.main_state,
+            &mut self.user_deposit,
+            &mut *self.deposit_tree.load_mut()?,
+            params.amount,
+            Clock::get()?.unix_timestamp,
+        )?;
+        self.unstake(params.amount, remaining_accounts)?;
+        Ok(())
+    }
+}
+
+/// Debits `amount` from `user_deposit`, whatever the tokens are unstaked to.
+pub(crate) fn record_withdrawal(
+    main_state: &mut MainState,
+    user_deposit: &mut UserDeposit,
+    deposit_tree: &mut DepositTree,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    if amount > user_deposit.amount {
+        return Err(error!(SolscatterError::WithdrawAmountExceedsDeposit));
+    }
+
+    if user_deposit.is_sponsor {
+        user_deposit.amount -= amount;
+        main_state.total_sponsor_deposit -= amount;
+        return Ok(());
+    }
+
+    let twab = main_state.calculate_twab(amount, now);
+
+    user_deposit.sync_twab(main_state.current_round);
+    let twab = std::cmp::min(twab, user_deposit.twab);
+    user_deposit.amount -= amount;
+    user_deposit.twab -= twab;
+
+    main_state.total_deposit -= amount;
+
+    deposit_tree.sub(user_deposit.slot, amount, twab)?;
+    Ok(())
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>, params: WithdrawParams) -> Result<()> {
+    if params.amount == 0 {
+        return Ok(());
+    }
+
+    ctx.accounts.withdraw(params, ctx.remaining_accounts)
+}
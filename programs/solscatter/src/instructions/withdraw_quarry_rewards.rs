@@ -0,0 +1,61 @@
+use crate::{
+    error::SolscatterError,
+    state::main_state::MainState,
+    MAIN_STATE_SEED,
+    QUARRY_REWARDS_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Moves quarry rewards that are not in the underlying mint out for swapping.
+/// The swapped vault tokens are sent back to the vault, where the next harvest adds them to the prize pool.
+#[derive(Accounts)]
+pub struct WithdrawQuarryRewards<'info> {
+    #[account(
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub admin: Signer<'info>,
+    /// Program-owned account holding the mining rewards
+    #[account(
+        mut,
+        seeds = [QUARRY_REWARDS_SEED, main_state.key().as_ref()],
+        bump,
+        constraint = rewards_tokens.mint != main_state.underlying_mint @ SolscatterError::InvalidYieldSourceAccounts,
+    )]
+    pub rewards_tokens: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount] receiving the rewards in the rewards mint.
+    #[account(
+        mut,
+        constraint = destination_tokens.mint == rewards_tokens.mint,
+    )]
+    pub destination_tokens: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawQuarryRewards<'info> {
+    fn into_transfer_cpi_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.rewards_tokens.to_account_info(),
+                to: self.destination_tokens.to_account_info(),
+                authority: self.main_state.to_account_info(),
+            },
+        )
+    }
+}
+
+pub fn handler(ctx: Context<WithdrawQuarryRewards>, amount: u64) -> Result<()> {
+    let signer_seeds = ctx.accounts.main_state.signer_seeds();
+    token::transfer(
+        ctx.accounts.into_transfer_cpi_context().with_signer(&[&signer_seeds]),
+        amount,
+    )
+}
@@ -0,0 +1,106 @@
+use crate::{
+    error::SolscatterError,
+    instructions::withdraw::{record_withdrawal, WithdrawParams},
+    state::{main_state::{MainState, PAUSE_WITHDRAW}, user_deposit::UserDeposit, DepositTree},
+    yield_source::PoolVault,
+    MAIN_STATE_SEED,
+    WRAPPED_SOL_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount};
+use spl_token::native_mint;
+
+/// Withdraws from a SOL pool as lamports, unwrapping the wSOL on the way.
+///
+/// Remaining accounts: the accounts of the pool's [YieldSource](crate::yield_source::YieldSource) adapter.
+#[derive(Accounts)]
+pub struct WithdrawSol<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = user_deposit.pool == main_state.key() @ SolscatterError::InvalidUserDepositAccount,
+    )]
+    pub user_deposit: Account<'info, UserDeposit>,
+    #[account(
+        mut,
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        constraint = !main_state.is_paused(PAUSE_WITHDRAW) @ SolscatterError::Paused,
+        constraint = main_state.underlying_mint == native_mint::id() @ SolscatterError::NotSolPool,
+    )]
+    pub main_state: Account<'info, MainState>,
+    #[account(
+        mut,
+        address = main_state.deposit_tree,
+    )]
+    pub deposit_tree: AccountLoader<'info, DepositTree>,
+    /// Receives the withdrawn lamports.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Temporary wSOL account, closed back to [Self::owner] once the withdrawal is unstaked.
+    #[account(
+        init,
+        payer = owner,
+        seeds = [WRAPPED_SOL_SEED, user_deposit.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = owner,
+    )]
+    pub wrapped_sol_tokens: Box<Account<'info, TokenAccount>>,
+    #[account(address = native_mint::id())]
+    pub native_mint: Box<Account<'info, Mint>>,
+    /// Program-owned vault paying out the withdrawal.
+    #[account(
+        mut,
+        address = main_state.vault,
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawSol<'info> {
+    fn into_close_account_context(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.wrapped_sol_tokens.to_account_info(),
+                destination: self.owner.to_account_info(),
+                authority: self.owner.to_account_info(),
+            },
+        )
+    }
+
+    fn unstake(&self, amount: u64, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let yield_source = PoolVault::new(&self.main_state, &self.vault, self.token_program.to_account_info())
+            .load_yield_source(remaining_accounts)?;
+        yield_source.withdraw(&self.wrapped_sol_tokens.to_account_info(), amount)
+    }
+
+    /// `params.amount` is in lamports.
+    pub fn withdraw_sol(&mut self, params: WithdrawParams, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        if params.amount > 0 {
+            record_withdrawal(
+                &mut self.main_state,
+                &mut self.user_deposit,
+                &mut *self.deposit_tree.load_mut()?,
+                params.amount,
+                Clock::get()?.unix_timestamp,
+            )?;
+            self.unstake(params.amount, remaining_accounts)?;
+        }
+
+        // closing the wSOL account pays out its lamports, unwrapping the withdrawal
+        token::close_account(self.into_close_account_context())
+    }
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawSol<'info>>, params: WithdrawParams) -> Result<()> {
+    ctx.accounts.withdraw_sol(params, ctx.remaining_accounts)
+}
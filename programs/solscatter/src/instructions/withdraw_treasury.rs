@@ -0,0 +1,53 @@
+use crate::{
+    state::main_state::MainState,
+    MAIN_STATE_SEED,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [
+            MAIN_STATE_SEED,
+            main_state.underlying_mint.as_ref(),
+            main_state.pool_id.to_le_bytes().as_ref(),
+        ],
+        bump = main_state.bump,
+        has_one = admin,
+        has_one = treasury,
+    )]
+    pub main_state: Account<'info, MainState>,
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+    /// The [TokenAccount] receiving the fees in the vault mint.
+    #[account(
+        mut,
+        constraint = destination_tokens.mint == treasury.mint,
+    )]
+    pub destination_tokens: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawTreasury<'info> {
+    fn into_transfer_cpi_context(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        CpiContext::new(
+            self.token_program.to_account_info(),
+            Transfer {
+                from: self.treasury.to_account_info(),
+                to: self.destination_tokens.to_account_info(),
+                authority: self.main_state.to_account_info(),
+            },
+        )
+    }
+}
+
+/// `amount` is in the vault mint, e.g. yi-solUST.
+pub fn handler(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let signer_seeds = ctx.accounts.main_state.signer_seeds();
+    token::transfer(
+        ctx.accounts.into_transfer_cpi_context().with_signer(&[&signer_seeds]),
+        amount,
+    )
+}
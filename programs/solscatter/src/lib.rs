@@ -2,6 +2,8 @@ mod error;
 mod events;
 mod instructions;
 mod state;
+mod utils;
+mod yield_source;
 
 use anchor_lang::prelude::*;
 use instructions::*;
@@ -10,14 +12,25 @@ declare_id!("DFyrxbvQJANhzH8Q6xLYFfEVKRyF6HzhVeWKGYjGzCfS");
 
 pub const STATE_SEED: &[u8] = b"STATE";
 pub const MAIN_STATE_SEED: &[u8] = b"main_state";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const MINER_VAULT_SEED: &[u8] = b"miner_vault";
+pub const QUARRY_REWARDS_SEED: &[u8] = b"quarry_rewards";
+pub const WRAPPED_SOL_SEED: &[u8] = b"wrapped_sol";
+pub const USER_SEED: &[u8] = b"user";
+
+#[cfg(feature = "mainnet")]
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = switchboard_v2::SWITCHBOARD_V2_MAINNET;
+#[cfg(not(feature = "mainnet"))]
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey = switchboard_v2::SWITCHBOARD_V2_DEVNET;
 
 #[program]
 pub mod solscatter {
     use super::*;
 
-    #[access_control(ctx.accounts.validate(&ctx))]
-    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        instructions::initialize::handler(ctx)
+    #[access_control(ctx.accounts.validate(&ctx, &params))]
+    pub fn initialize(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
+        instructions::initialize::handler(ctx, params)
     }
 
     pub fn callback_request_randomness(ctx: Context<CallbackRequestRandomness>) -> Result<()> {
@@ -33,15 +46,89 @@ pub mod solscatter {
         instructions::deposit_initialize::handler(ctx)
     }
 
-    pub fn deposit(ctx: Context<Deposit>, params: DepositParams) -> Result<()> {
+    pub fn deposit<'info>(ctx: Context<'_, '_, '_, 'info, Deposit<'info>>, params: DepositParams) -> Result<()> {
         instructions::deposit::handler(ctx, params)
     }
 
-    pub fn start_drawing_phase(ctx: Context<StartDrawingPhase>, number_of_rewards: u8, random_numbers: Vec<u64>) -> Result<()> {
-        instructions::start_drawing_phase::handler(ctx, number_of_rewards, random_numbers)
+    pub fn withdraw<'info>(ctx: Context<'_, '_, '_, 'info, Withdraw<'info>>, params: WithdrawParams) -> Result<()> {
+        instructions::withdraw::handler(ctx, params)
+    }
+
+    pub fn deposit_sol<'info>(ctx: Context<'_, '_, '_, 'info, DepositSol<'info>>, params: DepositParams) -> Result<()> {
+        instructions::deposit_sol::handler(ctx, params)
+    }
+
+    pub fn withdraw_sol<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawSol<'info>>, params: WithdrawParams) -> Result<()> {
+        instructions::withdraw_sol::handler(ctx, params)
+    }
+
+    pub fn harvest<'info>(ctx: Context<'_, '_, '_, 'info, Harvest<'info>>) -> Result<()> {
+        instructions::harvest::handler(ctx)
+    }
+
+    pub fn initialize_miner(ctx: Context<InitializeMiner>) -> Result<()> {
+        instructions::initialize_miner::handler(ctx)
+    }
+
+    pub fn claim_quarry_rewards<'info>(ctx: Context<'_, '_, '_, 'info, ClaimQuarryRewards<'info>>) -> Result<()> {
+        instructions::claim_quarry_rewards::handler(ctx)
+    }
+
+    pub fn withdraw_quarry_rewards(ctx: Context<WithdrawQuarryRewards>, amount: u64) -> Result<()> {
+        instructions::withdraw_quarry_rewards::handler(ctx, amount)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        instructions::withdraw_treasury::handler(ctx, amount)
+    }
+
+    pub fn start_drawing_phase(ctx: Context<StartDrawingPhase>) -> Result<()> {
+        instructions::start_drawing_phase::handler(ctx)
+    }
+
+    pub fn initialize_winners_page(ctx: Context<InitializeWinnersPage>) -> Result<()> {
+        instructions::initialize_winners_page::handler(ctx)
     }
 
-    pub fn drawing(ctx: Context<Drawing>) -> Result<()> {
+    pub fn drawing<'info>(ctx: Context<'_, '_, '_, 'info, Drawing<'info>>) -> Result<()> {
         instructions::drawing::handler(ctx)
     }
+
+    pub fn abort_drawing(ctx: Context<AbortDrawing>) -> Result<()> {
+        instructions::abort_drawing::handler(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate(&params))]
+    pub fn claim_prize<'info>(ctx: Context<'_, '_, '_, 'info, ClaimPrize<'info>>, params: ClaimPrizeParams) -> Result<()> {
+        instructions::claim_prize::handler(ctx, params)
+    }
+
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>) -> Result<()> {
+        instructions::sweep_unclaimed::handler(ctx)
+    }
+
+    #[access_control(ctx.accounts.validate(&params))]
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_admin::handler(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, pause_flags: u8) -> Result<()> {
+        instructions::set_pause::handler(ctx, pause_flags)
+    }
+
+    pub fn set_auto_compound(ctx: Context<SetAutoCompound>, auto_compound: bool) -> Result<()> {
+        instructions::set_auto_compound::handler(ctx, auto_compound)
+    }
+
+    pub fn set_sponsor(ctx: Context<SetSponsor>, is_sponsor: bool) -> Result<()> {
+        instructions::set_sponsor::handler(ctx, is_sponsor)
+    }
 }
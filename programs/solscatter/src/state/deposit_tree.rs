@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SolscatterError;
+
+/// Number of deposit slots the tree can hold. Must be a power of two.
+pub const DEPOSIT_TREE_CAPACITY: usize = 1 << 16;
+
+/// Fenwick trees of [UserDeposit](crate::state::UserDeposit) balances and
+/// time-weighted average balances keyed by slot.
+/// `nodes[i - 1]` holds the partial sum for slot `i`.
+#[account(zero_copy)]
+pub struct DepositTree {
+    pub balances: [u64; DEPOSIT_TREE_CAPACITY],
+    pub twabs: [u64; DEPOSIT_TREE_CAPACITY],
+}
+
+impl DepositTree {
+    pub const LEN: usize = 8 + 2 * 8 * DEPOSIT_TREE_CAPACITY;
+
+    pub fn add(&mut self, slot: u64, amount: u64, twab: u64) -> Result<()> {
+        let slot = Self::validate_slot(slot)?;
+        Self::update(&mut self.balances, slot, |node| node.checked_add(amount))?;
+        Self::update(&mut self.twabs, slot, |node| node.checked_add(twab))
+    }
+
+    pub fn sub(&mut self, slot: u64, amount: u64, twab: u64) -> Result<()> {
+        let slot = Self::validate_slot(slot)?;
+        Self::update(&mut self.balances, slot, |node| node.checked_sub(amount))?;
+        Self::update(&mut self.twabs, slot, |node| node.checked_sub(twab))
+    }
+
+    pub fn total_twab(&self) -> u64 {
+        self.twabs[DEPOSIT_TREE_CAPACITY - 1]
+    }
+
+    /// Every balance was held for the whole of the new round, so its average equals the balance.
+    pub fn reset_twabs(&mut self) {
+        self.twabs.copy_from_slice(&self.balances);
+    }
+
+    /// Returns the slot whose cumulative TWAB range `[from, to)` contains `random_number`.
+    pub fn find_slot(&self, random_number: u64) -> Result<u64> {
+        if random_number >= self.total_twab() {
+            return Err(error!(SolscatterError::RandomNumberOutOfRange));
+        }
+
+        let mut position: usize = 0;
+        let mut remaining = random_number;
+        let mut step = DEPOSIT_TREE_CAPACITY;
+        while step > 0 {
+            let next = position + step;
+            if next <= DEPOSIT_TREE_CAPACITY && self.twabs[next - 1] <= remaining {
+                position = next;
+                remaining -= self.twabs[next - 1];
+            }
+            step >>= 1;
+        }
+
+        Ok(position as u64 + 1)
+    }
+
+    fn update(nodes: &mut [u64], slot: usize, op: impl Fn(u64) -> Option<u64>) -> Result<()> {
+        let mut index = slot;
+        while index <= DEPOSIT_TREE_CAPACITY {
+            nodes[index - 1] = op(nodes[index - 1]).ok_or(SolscatterError::MathOverflow)?;
+            index += index & index.wrapping_neg();
+        }
+        Ok(())
+    }
+
+    fn validate_slot(slot: u64) -> Result<usize> {
+        if slot == 0 || slot > DEPOSIT_TREE_CAPACITY as u64 {
+            return Err(error!(SolscatterError::DepositTreeFull));
+        }
+        Ok(slot as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instructions::{record_deposit, record_withdrawal};
+    use crate::state::fixtures::{as_tree, new_main_state, new_tree, new_user_deposit};
+    use crate::state::UserDeposit;
+
+    fn draw(tree: &DepositTree, random_numbers: &[u64]) -> Vec<u64> {
+        random_numbers
+            .iter()
+            .map(|random_number| tree.find_slot(*random_number).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn find_slot_maps_cumulative_ranges() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        tree.add(1, 100, 100).unwrap();
+        tree.add(2, 50, 50).unwrap();
+        tree.add(5, 10, 10).unwrap();
+
+        assert_eq!(tree.total_twab(), 160);
+        assert_eq!(draw(tree, &[0, 99, 100, 149, 150, 159]), vec![1, 1, 2, 2, 5, 5]);
+        assert!(tree.find_slot(160).is_err());
+    }
+
+    #[test]
+    fn find_slot_skips_empty_slots_up_to_capacity() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        tree.add(3, 20, 20).unwrap();
+        tree.add(DEPOSIT_TREE_CAPACITY as u64, 30, 30).unwrap();
+        tree.add(2, 10, 10).unwrap();
+        tree.sub(2, 10, 10).unwrap();
+
+        assert_eq!(draw(tree, &[0, 19, 20, 49]), vec![3, 3, DEPOSIT_TREE_CAPACITY as u64, DEPOSIT_TREE_CAPACITY as u64]);
+        assert!(tree.add(0, 1, 1).is_err());
+        assert!(tree.add(DEPOSIT_TREE_CAPACITY as u64 + 1, 1, 1).is_err());
+    }
+
+    #[test]
+    fn reset_twabs_weights_the_next_round_by_balance() {
+        let mut nodes = new_tree();
+        let tree = as_tree(&mut nodes);
+        // slot 1 deposited at the start of the round, slot 2 halfway through, slot 3 withdrew everything
+        tree.add(1, 100, 100).unwrap();
+        tree.add(2, 100, 50).unwrap();
+        tree.add(3, 40, 40).unwrap();
+        tree.sub(3, 40, 0).unwrap();
+        assert_eq!(tree.total_twab(), 190);
+        assert_eq!(draw(tree, &[149, 150, 189]), vec![2, 3, 3]);
+
+        tree.reset_twabs();
+
+        assert_eq!(tree.total_twab(), 200);
+        assert_eq!(draw(tree, &[0, 99, 100, 199]), vec![1, 1, 2, 2]);
+        assert!(tree.find_slot(200).is_err());
+    }
+
+    #[test]
+    fn winners_ignore_balance_changes_during_drawing() {
+        let random_numbers = [3, 120, 199, 260, 299];
+        // withdrawals by user index
+        let balance_changes: [(usize, u64); 3] = [(0, 100), (2, 40), (1, 60)];
+
+        let mut winners = vec![];
+        for order in [[0, 1, 2], [2, 0, 1], [1, 2, 0]] {
+            let mut nodes = new_tree();
+            let tree = as_tree(&mut nodes);
+            let mut main_state = new_main_state();
+            let mut user_deposits: Vec<UserDeposit> = (1..=3).map(new_user_deposit).collect();
+            for user_deposit in user_deposits.iter_mut() {
+                record_deposit(&mut main_state, user_deposit, tree, 100, 0).unwrap();
+            }
+
+            // mid-round timestamp, so only the drawing flag keeps the withdrawals from carrying TWAB
+            main_state.drawing_in_progress = true;
+            let now = 500;
+            assert_eq!(main_state.calculate_twab(100, now), 0);
+            assert!(record_deposit(&mut main_state, &mut user_deposits[1], tree, 500, now).is_err());
+            for index in order {
+                let (user, amount) = balance_changes[index];
+                record_withdrawal(&mut main_state, &mut user_deposits[user], tree, amount, now).unwrap();
+            }
+
+            assert_eq!(main_state.total_deposit, 100);
+            assert_eq!(tree.total_twab(), 300);
+            winners.push(draw(tree, &random_numbers));
+        }
+
+        assert_eq!(winners[0], vec![1, 2, 2, 3, 3]);
+        assert!(winners.iter().all(|round_winners| *round_winners == winners[0]));
+    }
+}
@@ -1,37 +1,111 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 
 use crate::error::SolscatterError;
+use crate::state::prize_tier::{PrizeTier, MAX_PRIZE_TIERS};
+use crate::state::drawing_winners_page::WINNERS_PER_PAGE;
 
+/// Winner records live in [DrawingWinnersPage](crate::state::DrawingWinnersPage)s,
+/// reward `i` in page `i / WINNERS_PER_PAGE`.
 #[account]
 pub struct DrawingResult {
+    pub pool: Pubkey,
     pub round: u64,
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
     pub state: DrawingState,
-    pub number_of_rewards: u8,
-    pub winners: Vec<Option<Pubkey>>,
-    pub random_numbers: Vec<u64>,
-    pub total_deposit: u64,
-    pub last_processed_slot: u64,
+    pub winner_policy: WinnerPolicy,
+    pub number_of_rewards: u16,
+    pub number_of_pages: u16,
+    pub initialized_pages: u16,
+    /// Rewards are drawn in order, so this is also the index of the next reward to draw.
+    pub drawn_rewards: u16,
+    pub prize_tiers: Vec<PrizeTier>,
+    pub tier_payouts: Vec<u64>,
+    pub random_seed: [u8; 32],
+    pub total_twab: u64,
+    pub prize_amount: u64,
+    /// Part of `prize_amount` auto-compounded into the winners' deposits while drawing.
+    pub compounded_amount: u64,
+    pub rollover_amount: u64,
+    /// Protocol fee taken from the round's yield.
+    pub fee_amount: u64,
+    pub claim_window: i64,
+    /// Prizes swept back into the pool after the claim window closed.
+    pub expired_amount: u64,
     pub finished_timestamp: Option<i64>,
 }
 
 impl DrawingResult {
-    pub fn space(number_of_rewards: u8) -> Result<usize> {
-        if number_of_rewards <= 0 {
-            return Err(error!(SolscatterError::NumberOfRewardsMustMoreThanZero));
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        8 + // round
+        8 + // round_start_timestamp
+        8 + // round_end_timestamp
+        1 + // state
+        1 + // winner_policy
+        2 + // number_of_rewards
+        2 + // number_of_pages
+        2 + // initialized_pages
+        2 + // drawn_rewards
+        4 + PrizeTier::LEN * MAX_PRIZE_TIERS + // prize_tiers
+        4 + 8 * MAX_PRIZE_TIERS + // tier_payouts
+        32 + // random_seed
+        8 + // total_twab
+        8 + // prize_amount
+        8 + // compounded_amount
+        8 + // rollover_amount
+        8 + // fee_amount
+        8 + // claim_window
+        8 + // expired_amount
+        9; // finished_timestamp
+
+    pub fn number_of_pages(number_of_rewards: u16) -> u16 {
+        ((number_of_rewards as usize + WINNERS_PER_PAGE - 1) / WINNERS_PER_PAGE) as u16
+    }
+
+    /// Derives the random number for the `redraw`th attempt at `reward_index` from the round's VRF result,
+    /// reduced into `[0, total_twab)`.
+    pub fn derive_random_number(&self, reward_index: u16, redraw: u8) -> u64 {
+        let hash = hashv(&[&self.random_seed, &reward_index.to_le_bytes(), &[redraw]]);
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&hash.to_bytes()[..8]);
+        u64::from_le_bytes(bytes) % self.total_twab
+    }
+
+    /// Checks that `reward_index` can be claimed at `now`.
+    pub fn validate_claim(&self, reward_index: u16, now: i64) -> Result<()> {
+        if self.state != DrawingState::Finished {
+            return Err(error!(SolscatterError::DrawingNotFinished));
         }
-        if number_of_rewards > 10 {
-            return Err(error!(SolscatterError::NumberOfRewardsMustLessOrEqualTen));
+
+        if self.is_claim_expired(now) {
+            return Err(error!(SolscatterError::ClaimExpired));
         }
-        return Ok(8 + // discriminator
-            8 + // round
-            1 + // state
-            1 + // number_of_rewards
-            4 * (33 * number_of_rewards as usize) + // winners
-            4 * (8 * number_of_rewards as usize) + // random_numbers
-            8 + // total_deposit
-            8 + // last_processed_slot
-            9 // finished_timestamp
-        );
+
+        if reward_index >= self.number_of_rewards {
+            return Err(error!(SolscatterError::InvalidRewardIndex));
+        }
+
+        Ok(())
+    }
+
+    pub fn is_claim_expired(&self, now: i64) -> bool {
+        match self.finished_timestamp {
+            Some(finished_timestamp) => now >= finished_timestamp + self.claim_window,
+            None => false,
+        }
+    }
+
+    /// Returns the tier index and payout of every reward, in reward order.
+    pub fn rewards(&self) -> impl Iterator<Item = (u8, u64)> + '_ {
+        self.prize_tiers
+            .iter()
+            .zip(self.tier_payouts.iter())
+            .enumerate()
+            .flat_map(|(tier, (prize_tier, payout))| {
+                (0..prize_tier.number_of_rewards).map(move |_| (tier as u8, *payout))
+            })
     }
 }
 
@@ -39,4 +113,40 @@ impl DrawingResult {
 pub enum DrawingState {
     Processing,
     Finished,
-}
\ No newline at end of file
+    /// No winner was drawn and the prize rolled into the next round.
+    Aborted,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub enum WinnerPolicy {
+    AllowDuplicates,
+    /// A depositor who already won this round is skipped and the reward drawn again
+    /// with the next random number, up to [MAX_REDRAWS] times.
+    Redraw,
+}
+
+pub const MAX_REDRAWS: u8 = 16;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::{assert_error, new_drawing_result};
+
+    #[test]
+    fn claims_close_with_the_claim_window() {
+        let drawing_result = new_drawing_result();
+
+        assert!(drawing_result.validate_claim(1, 999).is_ok());
+        assert_error(drawing_result.validate_claim(2, 999), SolscatterError::InvalidRewardIndex);
+        assert_error(drawing_result.validate_claim(0, 1_000), SolscatterError::ClaimExpired);
+    }
+
+    #[test]
+    fn claims_wait_for_the_drawing_to_finish() {
+        let mut drawing_result = new_drawing_result();
+        drawing_result.state = DrawingState::Processing;
+        drawing_result.finished_timestamp = None;
+
+        assert_error(drawing_result.validate_claim(0, 0), SolscatterError::DrawingNotFinished);
+    }
+}
@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SolscatterError;
+use crate::state::drawing_result::DrawingResult;
+
+pub const WINNERS_PER_PAGE: usize = 100;
+
+/// A page of winner records for one [DrawingResult](crate::state::DrawingResult).
+#[account(zero_copy)]
+pub struct DrawingWinnersPage {
+    pub drawing_result: Pubkey,
+    pub round: u64,
+    pub page_index: u32,
+    pub number_of_entries: u32,
+    pub entries: [WinnerEntry; WINNERS_PER_PAGE],
+}
+
+impl DrawingWinnersPage {
+    pub const LEN: usize = 8 + 32 + 8 + 4 + 4 + WinnerEntry::LEN * WINNERS_PER_PAGE;
+
+    /// Index of the first reward stored in this page.
+    pub fn first_reward_index(&self) -> usize {
+        self.page_index as usize * WINNERS_PER_PAGE
+    }
+
+    /// Fills page `page_index` of `drawing_result` with its rewards and their first random numbers.
+    pub fn initialize(&mut self, drawing_result_key: Pubkey, drawing_result: &DrawingResult, page_index: u16) {
+        self.drawing_result = drawing_result_key;
+        self.round = drawing_result.round;
+        self.page_index = page_index as u32;
+
+        let first_reward_index = self.first_reward_index();
+        let rewards = drawing_result
+            .rewards()
+            .enumerate()
+            .skip(first_reward_index)
+            .take(WINNERS_PER_PAGE);
+        for (reward_index, (tier, payout)) in rewards {
+            let entry = &mut self.entries[reward_index - first_reward_index];
+            entry.random_number = drawing_result.derive_random_number(reward_index as u16, 0);
+            entry.amount = payout;
+            entry.tier = tier;
+            self.number_of_entries += 1;
+        }
+    }
+
+    /// Marks every prize not claimed yet as expired and returns their total amount.
+    pub fn expire_unclaimed(&mut self) -> u64 {
+        let number_of_entries = self.number_of_entries as usize;
+        let mut expired_amount: u64 = 0;
+        for entry in self.entries[..number_of_entries].iter_mut() {
+            if entry.is_claimed() || entry.is_expired() {
+                continue;
+            }
+            entry.expired = 1;
+            expired_amount += entry.amount;
+        }
+        expired_amount
+    }
+
+    pub fn holds_reward(&self, reward_index: usize) -> bool {
+        let first_reward_index = self.first_reward_index();
+        reward_index >= first_reward_index && reward_index < first_reward_index + self.number_of_entries as usize
+    }
+}
+
+#[zero_copy]
+pub struct WinnerEntry {
+    pub winner: Pubkey,
+    pub random_number: u64,
+    pub amount: u64,
+    pub tier: u8,
+    pub drawn: u8,
+    pub claimed: u8,
+    pub redraws: u8,
+    pub expired: u8,
+    pub padding: [u8; 3],
+}
+
+impl WinnerEntry {
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 1 + 1 + 1 + 1 + 3;
+
+    pub fn is_drawn(&self) -> bool {
+        self.drawn != 0
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        self.claimed != 0
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expired != 0
+    }
+
+    /// Marks the prize claimed by `winner` and returns its amount.
+    pub fn claim(&mut self, winner: Pubkey) -> Result<u64> {
+        if !self.is_drawn() || self.winner != winner {
+            return Err(error!(SolscatterError::NotWinner));
+        }
+
+        if self.is_claimed() {
+            return Err(error!(SolscatterError::PrizeAlreadyClaimed));
+        }
+
+        // already returned to the prize pool
+        if self.is_expired() {
+            return Err(error!(SolscatterError::ClaimExpired));
+        }
+
+        self.claimed = 1;
+        Ok(self.amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::{assert_error, new_drawing_result, new_main_state, new_winner_entry};
+    use crate::state::PrizeTier;
+
+    #[test]
+    fn pages_hold_rewards_in_reward_order() {
+        let mut drawing_result = new_drawing_result();
+        drawing_result.prize_tiers = vec![
+            PrizeTier { number_of_rewards: 1, share_bps: 5_000 },
+            PrizeTier { number_of_rewards: 149, share_bps: 5_000 },
+        ];
+        drawing_result.tier_payouts = vec![500, 3];
+        drawing_result.number_of_rewards = 150;
+        drawing_result.total_twab = 1_000;
+        assert_eq!(DrawingResult::number_of_pages(100), 1);
+        assert_eq!(DrawingResult::number_of_pages(150), 2);
+
+        let pages: Vec<DrawingWinnersPage> = (0..2)
+            .map(|page_index| {
+                let mut page: DrawingWinnersPage = bytemuck::Zeroable::zeroed();
+                page.initialize(Pubkey::default(), &drawing_result, page_index);
+                page
+            })
+            .collect();
+
+        assert_eq!(pages[0].number_of_entries, 100);
+        assert_eq!(pages[1].number_of_entries, 50);
+        assert_eq!((pages[0].entries[0].tier, pages[0].entries[0].amount), (0, 500));
+        assert_eq!((pages[0].entries[1].tier, pages[0].entries[1].amount), (1, 3));
+        assert_eq!((pages[1].entries[49].tier, pages[1].entries[49].amount), (1, 3));
+        assert_eq!(pages[1].entries[0].random_number, drawing_result.derive_random_number(100, 0));
+        let total_amount: u64 = pages
+            .iter()
+            .flat_map(|page| page.entries[..page.number_of_entries as usize].iter())
+            .map(|entry| entry.amount)
+            .sum();
+        assert_eq!(total_amount, 500 + 149 * 3);
+
+        assert!(pages[0].holds_reward(0) && pages[0].holds_reward(99) && !pages[0].holds_reward(100));
+        assert!(pages[1].holds_reward(100) && pages[1].holds_reward(149));
+        assert!(!pages[1].holds_reward(99) && !pages[1].holds_reward(150));
+    }
+
+    #[test]
+    fn a_prize_is_claimed_once_by_its_winner() {
+        let winner = Pubkey::new_unique();
+        let mut entry = new_winner_entry(winner, 50);
+
+        assert_error(entry.claim(Pubkey::new_unique()), SolscatterError::NotWinner);
+        assert_eq!(entry.claim(winner).unwrap(), 50);
+        assert!(entry.is_claimed());
+        assert_error(entry.claim(winner), SolscatterError::PrizeAlreadyClaimed);
+    }
+
+    #[test]
+    fn an_undrawn_prize_has_no_winner() {
+        let mut entry = new_winner_entry(Pubkey::default(), 50);
+        entry.drawn = 0;
+
+        assert_error(entry.claim(Pubkey::default()), SolscatterError::NotWinner);
+        assert!(!entry.is_claimed());
+    }
+
+    #[test]
+    fn expired_prizes_roll_into_the_prize_pool() {
+        let winner = Pubkey::new_unique();
+        let mut page: DrawingWinnersPage = bytemuck::Zeroable::zeroed();
+        page.entries[0] = new_winner_entry(winner, 50);
+        page.entries[1] = new_winner_entry(winner, 30);
+        page.entries[2] = new_winner_entry(winner, 20);
+        page.number_of_entries = 3;
+        page.entries[1].claim(winner).unwrap();
+
+        let mut main_state = new_main_state();
+        main_state.prize_pool = 5;
+        main_state.rollover_amount = 5;
+        main_state.unclaimed_prize = 70;
+
+        let expired_amount = page.expire_unclaimed();
+        main_state.roll_over(expired_amount);
+
+        assert_eq!(expired_amount, 70);
+        assert!(page.entries[0].is_expired() && !page.entries[1].is_expired() && page.entries[2].is_expired());
+        assert_eq!(main_state.unclaimed_prize, 0);
+        assert_eq!(main_state.prize_pool, 75);
+        assert_eq!(main_state.rollover_amount, 75);
+        // a second sweep finds nothing left
+        assert_eq!(page.expire_unclaimed(), 0);
+        assert_error(page.entries[0].claim(winner), SolscatterError::ClaimExpired);
+    }
+}
@@ -0,0 +1,119 @@
+//! Off-chain stand-ins for the program's accounts, shared by the unit tests.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_error::ProgramError;
+
+use crate::error::SolscatterError;
+use crate::state::{
+    DepositTree, DrawingResult, DrawingState, MainState, PrizeTier, UserDeposit, WinnerEntry, WinnerPolicy,
+    DEPOSIT_TREE_CAPACITY,
+};
+use crate::yield_source::YieldSource;
+
+pub fn assert_error<T: std::fmt::Debug>(result: Result<T>, expected: SolscatterError) {
+    assert_eq!(ProgramError::from(result.unwrap_err()), ProgramError::Custom(expected.into()));
+}
+
+pub fn new_tree() -> Vec<u64> {
+    vec![0u64; 2 * DEPOSIT_TREE_CAPACITY]
+}
+
+pub fn as_tree(nodes: &mut [u64]) -> &mut DepositTree {
+    bytemuck::from_bytes_mut(bytemuck::cast_slice_mut(nodes))
+}
+
+pub fn new_main_state() -> MainState {
+    MainState {
+        underlying_mint: Pubkey::default(),
+        pool_id: 0,
+        yield_source: YieldSource::NoYield,
+        admin: Pubkey::default(),
+        pending_admin: None,
+        number_of_rewards: 1,
+        prize_tiers: vec![],
+        winner_policy: WinnerPolicy::AllowDuplicates,
+        pause_flags: 0,
+        round_duration: 1_000,
+        deposit_cutoff: 0,
+        claim_window: 0,
+        round_start_timestamp: 0,
+        round_end_timestamp: 1_000,
+        drawing_in_progress: false,
+        current_slot: 0,
+        current_round: 1,
+        total_deposit: 0,
+        total_sponsor_deposit: 0,
+        vrf_account_pubkey: Pubkey::default(),
+        vault: Pubkey::default(),
+        treasury: Pubkey::default(),
+        miner: Pubkey::default(),
+        fee_bps: 0,
+        round_fee_amount: 0,
+        bump: 0,
+        last_vrf_timestamp: 0,
+        prize_pool: 0,
+        unclaimed_prize: 0,
+        rollover_amount: 0,
+        deposit_tree: Pubkey::default(),
+    }
+}
+
+pub fn new_user_deposit(slot: u64) -> UserDeposit {
+    UserDeposit {
+        pool: Pubkey::default(),
+        slot,
+        amount: 0,
+        owner: Pubkey::default(),
+        latest_deposit_timestamp: None,
+        bump: 0,
+        twab: 0,
+        twab_round: 1,
+        last_won_round: 0,
+        auto_compound: false,
+        is_sponsor: false,
+    }
+}
+
+/// A finished drawing of two rewards of 50, claimable until 1_000.
+pub fn new_drawing_result() -> DrawingResult {
+    DrawingResult {
+        pool: Pubkey::default(),
+        round: 1,
+        round_start_timestamp: 0,
+        round_end_timestamp: 0,
+        state: DrawingState::Finished,
+        winner_policy: WinnerPolicy::AllowDuplicates,
+        number_of_rewards: 2,
+        number_of_pages: 1,
+        initialized_pages: 1,
+        drawn_rewards: 2,
+        prize_tiers: vec![PrizeTier {
+            number_of_rewards: 2,
+            share_bps: 10_000,
+        }],
+        tier_payouts: vec![50],
+        random_seed: [0u8; 32],
+        total_twab: 0,
+        prize_amount: 100,
+        compounded_amount: 0,
+        rollover_amount: 0,
+        fee_amount: 0,
+        claim_window: 1_000,
+        expired_amount: 0,
+        finished_timestamp: Some(0),
+    }
+}
+
+pub fn new_winner_entry(winner: Pubkey, amount: u64) -> WinnerEntry {
+    WinnerEntry {
+        winner,
+        random_number: 0,
+        amount,
+        tier: 0,
+        drawn: 1,
+        claimed: 0,
+        redraws: 0,
+        expired: 0,
+        padding: [0u8; 3],
+    }
+}
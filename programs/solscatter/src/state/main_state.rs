@@ -1,13 +1,139 @@
 use anchor_lang::prelude::*;
 
+use crate::MAIN_STATE_SEED;
+use crate::state::drawing_result::WinnerPolicy;
+use crate::yield_source::YieldSource;
+use crate::state::prize_tier::{PrizeTier, MAX_PRIZE_TIERS};
+
+pub const PAUSE_DEPOSIT: u8 = 1 << 0;
+pub const PAUSE_WITHDRAW: u8 = 1 << 1;
+pub const PAUSE_DRAWING: u8 = 1 << 2;
+pub const PAUSE_CLAIM: u8 = 1 << 3;
+
+pub const DEFAULT_ROUND_DURATION: i64 = 7 * 24 * 60 * 60;
+pub const DEFAULT_CLAIM_WINDOW: i64 = 30 * 24 * 60 * 60;
+
+/// A lottery pool, seeded by its underlying mint and `pool_id`.
 #[account]
 pub struct MainState {
+    pub underlying_mint: Pubkey,
+    pub pool_id: u64,
+    pub yield_source: YieldSource,
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub number_of_rewards: u16,
+    pub prize_tiers: Vec<PrizeTier>,
+    pub winner_policy: WinnerPolicy,
+    pub pause_flags: u8,
+    pub round_duration: i64,
+    pub deposit_cutoff: i64,
+    pub claim_window: i64,
+    pub round_start_timestamp: i64,
+    pub round_end_timestamp: i64,
+    pub drawing_in_progress: bool,
     pub current_slot: u64,
     pub current_round: u64,
     pub total_deposit: u64,
+    /// Sponsor principal, staked for yield but never drawn.
+    pub total_sponsor_deposit: u64,
     pub vrf_account_pubkey: Pubkey,
+    pub vault: Pubkey,
+    pub treasury: Pubkey,
+    /// Quarry miner of the vault, set by `initialize_miner` for [YieldSource::Quarry] pools.
+    pub miner: Pubkey,
+    /// Share of harvested yield sent to the treasury, in basis points.
+    pub fee_bps: u16,
+    /// Fees taken from the current round's yield so far.
+    pub round_fee_amount: u64,
+    pub bump: u8,
+    pub last_vrf_timestamp: i64,
+    pub prize_pool: u64,
+    pub unclaimed_prize: u64,
+    /// Part of `prize_pool` left unawarded by earlier rounds.
+    pub rollover_amount: u64,
+    pub deposit_tree: Pubkey,
 }
 
 impl MainState {
-    pub const LEN: usize = 8 + 8 + 8 + 8 + 32;
-}
\ No newline at end of file
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 32 + 33 + 2 + (4 + PrizeTier::LEN * MAX_PRIZE_TIERS) + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 8 + 32 + 32 + 32 + 32 + 2 + 8 + 1 + 8 + 8 + 8 + 8 + 32;
+
+    /// Seeds of the pool's address, signing for the accounts it owns.
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        [
+            MAIN_STATE_SEED,
+            self.underlying_mint.as_ref(),
+            // the in-memory bytes are the little-endian `pool_id` seed, programs only run on little-endian targets
+            bytemuck::bytes_of(&self.pool_id),
+            std::slice::from_ref(&self.bump),
+        ]
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    pub fn is_round_ended(&self, now: i64) -> bool {
+        now >= self.round_end_timestamp
+    }
+
+    /// Deposits close `deposit_cutoff` seconds before the round ends and reopen once it has been drawn.
+    pub fn is_deposit_closed(&self, now: i64) -> bool {
+        now >= self.round_end_timestamp - self.deposit_cutoff
+    }
+
+    /// Share of `amount` that counts towards the round's average balance when it moves at `now`.
+    /// Balances are snapshotted while a drawing is in progress, so nothing counts until the next round.
+    pub fn calculate_twab(&self, amount: u64, now: i64) -> u64 {
+        if self.drawing_in_progress {
+            return 0;
+        }
+
+        let duration = self.round_end_timestamp - self.round_start_timestamp;
+        if duration <= 0 {
+            return amount;
+        }
+
+        let remaining = (self.round_end_timestamp - now).clamp(0, duration);
+        (amount as u128 * remaining as u128 / duration as u128) as u64
+    }
+
+    /// Returns an unawarded prize to the pool so it rolls into the next round.
+    pub fn roll_over(&mut self, amount: u64) {
+        self.unclaimed_prize = self.unclaimed_prize.saturating_sub(amount);
+        self.prize_pool += amount;
+        self.rollover_amount += amount;
+    }
+
+    /// Starts the next round on the same cadence, skipping whole periods that were missed.
+    pub fn start_next_round(&mut self, now: i64) {
+        let mut start_timestamp = self.round_end_timestamp;
+        if now >= start_timestamp + self.round_duration {
+            let missed_rounds = (now - start_timestamp) / self.round_duration;
+            start_timestamp += missed_rounds * self.round_duration;
+        }
+
+        self.current_round += 1;
+        self.round_start_timestamp = start_timestamp;
+        self.round_end_timestamp = start_timestamp + self.round_duration;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::new_main_state;
+
+    #[test]
+    fn signer_seeds_derive_the_pool_address() {
+        let mut main_state = new_main_state();
+        main_state.underlying_mint = Pubkey::new_unique();
+        main_state.pool_id = 0x0102_0304_0506_0708;
+        let (pool, bump) = Pubkey::find_program_address(
+            &[MAIN_STATE_SEED, main_state.underlying_mint.as_ref(), &main_state.pool_id.to_le_bytes()],
+            &crate::ID,
+        );
+        main_state.bump = bump;
+
+        assert_eq!(Pubkey::create_program_address(&main_state.signer_seeds(), &crate::ID).unwrap(), pool);
+    }
+}
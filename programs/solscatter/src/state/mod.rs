@@ -1,9 +1,19 @@
 pub mod main_state;
 pub mod drawing_result;
+pub mod drawing_winners_page;
 pub mod user_deposit;
+pub mod user_deposit_index;
 pub mod vrf_client;
+pub mod deposit_tree;
+pub mod prize_tier;
+#[cfg(test)]
+pub mod fixtures;
 
 pub use main_state::*;
 pub use drawing_result::*;
+pub use drawing_winners_page::*;
 pub use user_deposit::*;
-pub use vrf_client::*;
\ No newline at end of file
+pub use user_deposit_index::*;
+pub use vrf_client::*;
+pub use deposit_tree::*;
+pub use prize_tier::*;
\ No newline at end of file
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::error::SolscatterError;
+
+pub const MAX_PRIZE_TIERS: usize = 5;
+pub const MAX_BPS: u64 = 10_000;
+pub const MAX_NUMBER_OF_REWARDS: u64 = 1_000;
+
+/// `number_of_rewards` winners each receive `share_bps` of the round's prize pool.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub struct PrizeTier {
+    pub number_of_rewards: u16,
+    pub share_bps: u16,
+}
+
+impl PrizeTier {
+    pub const LEN: usize = 2 + 2;
+
+    /// Validates a tier table and returns the total number of rewards it awards.
+    pub fn validate_tiers(prize_tiers: &[PrizeTier]) -> Result<u16> {
+        if prize_tiers.is_empty() || prize_tiers.len() > MAX_PRIZE_TIERS {
+            return Err(error!(SolscatterError::InvalidPrizeTiers));
+        }
+
+        let mut number_of_rewards: u64 = 0;
+        let mut total_share_bps: u64 = 0;
+        for prize_tier in prize_tiers {
+            if prize_tier.number_of_rewards == 0 {
+                return Err(error!(SolscatterError::NumberOfRewardsMustMoreThanZero));
+            }
+            number_of_rewards += prize_tier.number_of_rewards as u64;
+            total_share_bps += prize_tier.number_of_rewards as u64 * prize_tier.share_bps as u64;
+        }
+
+        if total_share_bps > MAX_BPS {
+            return Err(error!(SolscatterError::InvalidPrizeTiers));
+        }
+        if number_of_rewards > MAX_NUMBER_OF_REWARDS {
+            return Err(error!(SolscatterError::TooManyRewards));
+        }
+
+        Ok(number_of_rewards as u16)
+    }
+
+    pub fn calculate_payout(&self, prize_pool: u64) -> u64 {
+        (prize_pool as u128 * self.share_bps as u128 / MAX_BPS as u128) as u64
+    }
+}
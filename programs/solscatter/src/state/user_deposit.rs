@@ -2,12 +2,34 @@ use anchor_lang::prelude::*;
 
 #[account]
 pub struct UserDeposit {
+    pub pool: Pubkey,
     pub slot: u64,
     pub amount: u64,
     pub owner: Pubkey,
     pub latest_deposit_timestamp: Option<i64>,
+    pub bump: u8,
+    pub twab: u64,
+    pub twab_round: u64,
+    pub last_won_round: u64,
+    /// Prizes are added to `amount` when drawn instead of being claimed.
+    pub auto_compound: bool,
+    /// Sponsor deposits earn yield for the prize pool but are kept out of the [DepositTree](crate::state::DepositTree).
+    pub is_sponsor: bool,
 }
 
 impl UserDeposit {
-    pub const LEN: usize = 8 + 8 + 8 + 32 + 9;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 32 + 9 + 1 + 8 + 8 + 8 + 1 + 1;
+
+    /// A balance untouched since an earlier round was held for the whole current round.
+    pub fn sync_twab(&mut self, current_round: u64) {
+        if self.twab_round != current_round {
+            self.twab = self.amount;
+            self.twab_round = current_round;
+        }
+    }
+
+    /// Whether the deposit still holds a share of `current_round`'s drawing, e.g. after withdrawing mid-round.
+    pub fn has_twab(&self, current_round: u64) -> bool {
+        self.twab_round == current_round && self.twab > 0
+    }
 }
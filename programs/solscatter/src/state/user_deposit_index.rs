@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Maps a depositor to the slot of their [UserDeposit](crate::state::UserDeposit) in a pool,
+/// seeded by [USER_SEED](crate::USER_SEED), the pool and the owner.
+#[account]
+pub struct UserDepositIndex {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    /// Zero until the owner's first `deposit_initialize`, slots start at 1.
+    pub slot: u64,
+    pub bump: u8,
+}
+
+impl UserDepositIndex {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+
+    pub fn is_initialized(&self) -> bool {
+        self.slot != 0
+    }
+
+    /// Slot of the owner's deposit, or `next_slot` if none was allocated yet.
+    pub fn slot_or(&self, next_slot: u64) -> u64 {
+        if self.is_initialized() {
+            self.slot
+        } else {
+            next_slot
+        }
+    }
+}
@@ -2,12 +2,23 @@ use anchor_lang::prelude::*;
 
 #[account(zero_copy)]
 pub struct VrfClientState {
+    /// The pool seeding this account, its admin requests the randomness.
     pub authority: Pubkey,
     pub max_result: u64,
     pub vrf: Pubkey,
     pub result_buffer: [u8; 32],
     pub result: u128,
     pub last_timestamp: i64,
+    /// The [MainState](crate::state::MainState) pool drawing with this VRF.
+    pub pool: Pubkey,
+}
+
+impl VrfClientState {
+    /// Whether the stored result was produced after the round ending at `round_end_timestamp`.
+    /// That result is kept for the round's drawing, so it cannot be re-rolled by requesting again.
+    pub fn has_round_result(&self, round_end_timestamp: i64) -> bool {
+        self.last_timestamp >= round_end_timestamp
+    }
 }
 
 impl Default for VrfClientState {
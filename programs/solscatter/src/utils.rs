@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{program::invoke, system_instruction};
+
+use crate::error::SolscatterError;
+
+/// Converts an amount of solUST into yi-solUST at the current yi exchange rate.
+pub fn calculate_yi_amount(amount: u64, total_underlying_tokens: u64, total_supply: u64) -> Result<u64> {
+    if total_underlying_tokens == 0 {
+        return Ok(amount);
+    }
+
+    let yi_amount = (amount as u128)
+        .checked_mul(total_supply as u128)
+        .and_then(|value| value.checked_div(total_underlying_tokens as u128))
+        .ok_or(SolscatterError::MathOverflow)?;
+
+    Ok(yi_amount as u64)
+}
+
+/// Converts an amount of yi-solUST into solUST at the current yi exchange rate.
+pub fn calculate_underlying_amount(yi_amount: u64, total_underlying_tokens: u64, total_supply: u64) -> Result<u64> {
+    if total_supply == 0 {
+        return Ok(0);
+    }
+
+    let amount = (yi_amount as u128)
+        .checked_mul(total_underlying_tokens as u128)
+        .and_then(|value| value.checked_div(total_supply as u128))
+        .ok_or(SolscatterError::MathOverflow)?;
+
+    Ok(amount as u64)
+}
+
+/// Moves `amount` lamports from `payer` into the wSOL account `wrapped_sol_tokens` and syncs its token balance.
+pub fn wrap_sol<'info>(
+    payer: &AccountInfo<'info>,
+    wrapped_sol_tokens: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    invoke(
+        &system_instruction::transfer(payer.key, wrapped_sol_tokens.key, amount),
+        &[payer.clone(), wrapped_sol_tokens.clone(), system_program.clone()],
+    )?;
+    invoke(
+        &spl_token::instruction::sync_native(token_program.key, wrapped_sol_tokens.key)?,
+        &[wrapped_sol_tokens.clone(), token_program.clone()],
+    )?;
+    Ok(())
+}
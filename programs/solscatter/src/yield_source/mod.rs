@@ -0,0 +1,85 @@
+pub mod yi;
+pub mod no_yield;
+pub mod quarry;
+
+pub use self::yi::*;
+pub use no_yield::*;
+pub use quarry::*;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::state::main_state::MainState;
+
+/// Where a pool puts its deposits to earn the yield that funds the prizes.
+/// Fixed when the pool is initialized, since the vault holds the source's token.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, AnchorSerialize, AnchorDeserialize)]
+pub enum YieldSource {
+    /// Stakes into the yi program, the vault holds yi tokens.
+    Yi,
+    /// Stakes into the yi program and mines the yi tokens in quarry.
+    Quarry,
+    /// Keeps the underlying tokens in the vault, for tests.
+    NoYield,
+}
+
+/// Operations every yield source implements.
+/// Amounts are in the pool's underlying token, conversions to what the vault holds are internal.
+pub trait YieldSourceAdapter<'info> {
+    /// Moves `amount` from `source_tokens`, owned by the signing `source_authority`, into the vault.
+    fn deposit(&self, source_tokens: &AccountInfo<'info>, source_authority: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    /// Moves `amount` from `source_tokens`, owned by the pool, into the vault.
+    fn deposit_from_pool(&self, source_tokens: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    /// Pays `amount` out of the vault into `destination_tokens`.
+    fn withdraw(&self, destination_tokens: &AccountInfo<'info>, amount: u64) -> Result<()>;
+
+    /// Current value of the vault.
+    fn current_value(&self) -> Result<u64>;
+
+    /// Amount of vault tokens worth `amount`.
+    fn to_vault_amount(&self, amount: u64) -> Result<u64>;
+
+    /// Makes sure `vault_amount` vault tokens sit in the vault itself, before they are transferred out of it.
+    fn unstake_to_vault(&self, _vault_amount: u64) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The pool vault and everything needed to sign for it.
+pub struct PoolVault<'a, 'info> {
+    pub main_state: &'a Account<'info, MainState>,
+    pub vault: &'a Account<'info, TokenAccount>,
+    pub token_program: AccountInfo<'info>,
+}
+
+impl<'a, 'info> PoolVault<'a, 'info> {
+    pub fn new(
+        main_state: &'a Account<'info, MainState>,
+        vault: &'a Account<'info, TokenAccount>,
+        token_program: AccountInfo<'info>,
+    ) -> Self {
+        Self {
+            main_state,
+            vault,
+            token_program,
+        }
+    }
+
+    pub fn signer_seeds(&self) -> [&[u8]; 4] {
+        self.main_state.signer_seeds()
+    }
+
+    /// Loads the adapter of the pool's yield source from the accounts it expects in `remaining_accounts`.
+    pub fn load_yield_source(
+        self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Box<dyn YieldSourceAdapter<'info> + 'a>> {
+        match self.main_state.yield_source {
+            YieldSource::Yi => Ok(Box::new(YiAdapter::load(self, remaining_accounts)?)),
+            YieldSource::NoYield => Ok(Box::new(NoYieldAdapter::load(self)?)),
+            YieldSource::Quarry => Ok(Box::new(QuarryAdapter::load(self, remaining_accounts)?)),
+        }
+    }
+}
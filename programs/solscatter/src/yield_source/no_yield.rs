@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+
+use crate::error::SolscatterError;
+use crate::yield_source::{PoolVault, YieldSourceAdapter};
+
+/// Holds the underlying tokens as they are. Prizes only come from tokens sent to the vault directly.
+pub struct NoYieldAdapter<'a, 'info> {
+    pool_vault: PoolVault<'a, 'info>,
+}
+
+impl<'a, 'info> NoYieldAdapter<'a, 'info> {
+    pub fn load(pool_vault: PoolVault<'a, 'info>) -> Result<Self> {
+        if pool_vault.vault.mint != pool_vault.main_state.underlying_mint {
+            return Err(error!(SolscatterError::InvalidYieldSourceAccounts));
+        }
+        Ok(Self { pool_vault })
+    }
+}
+
+impl<'a, 'info> YieldSourceAdapter<'info> for NoYieldAdapter<'a, 'info> {
+    fn deposit(&self, source_tokens: &AccountInfo<'info>, source_authority: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        token::transfer(
+            CpiContext::new(
+                self.pool_vault.token_program.clone(),
+                Transfer {
+                    from: source_tokens.clone(),
+                    to: self.pool_vault.vault.to_account_info(),
+                    authority: source_authority.clone(),
+                },
+            ),
+            amount,
+        )
+    }
+
+    fn deposit_from_pool(&self, source_tokens: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let signer_seeds = self.pool_vault.signer_seeds();
+        token::transfer(
+            CpiContext::new(
+                self.pool_vault.token_program.clone(),
+                Transfer {
+                    from: source_tokens.clone(),
+                    to: self.pool_vault.vault.to_account_info(),
+                    authority: self.pool_vault.main_state.to_account_info(),
+                },
+            )
+            .with_signer(&[&signer_seeds]),
+            amount,
+        )
+    }
+
+    fn withdraw(&self, destination_tokens: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let signer_seeds = self.pool_vault.signer_seeds();
+        token::transfer(
+            CpiContext::new(
+                self.pool_vault.token_program.clone(),
+                Transfer {
+                    from: self.pool_vault.vault.to_account_info(),
+                    to: destination_tokens.clone(),
+                    authority: self.pool_vault.main_state.to_account_info(),
+                },
+            )
+            .with_signer(&[&signer_seeds]),
+            std::cmp::min(amount, self.pool_vault.vault.amount),
+        )
+    }
+
+    fn current_value(&self) -> Result<u64> {
+        Ok(self.pool_vault.vault.amount)
+    }
+
+    fn to_vault_amount(&self, amount: u64) -> Result<u64> {
+        Ok(amount)
+    }
+}
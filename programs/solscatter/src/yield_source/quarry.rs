@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount};
+use quarry_mine::{cpi::accounts::UserStake, Miner, Quarry, Rewarder};
+
+use crate::error::SolscatterError;
+use crate::yield_source::{PoolVault, YiAdapter, YieldSourceAdapter};
+
+/// Stakes the underlying tokens into a [yi::YiToken] like [YiAdapter], then mines the yi tokens in a [Quarry].
+/// The pool's [Miner] is created by `initialize_miner`, deposits only pass through the vault.
+///
+/// Remaining accounts:
+/// 0. - 3. the [YiAdapter] accounts
+/// 4. `[]` quarry mine program
+/// 5. `[writable]` the pool's [Miner]
+/// 6. `[writable]` [Miner::quarry_key]
+/// 7. `[writable]` [Miner::token_vault_key]
+/// 8. `[]` [Quarry::rewarder_key]
+pub struct QuarryAdapter<'a, 'info> {
+    yi: YiAdapter<'a, 'info>,
+    quarry_mine_program: AccountInfo<'info>,
+    miner: Account<'info, Miner>,
+    quarry: Account<'info, Quarry>,
+    miner_vault: Account<'info, TokenAccount>,
+    rewarder: Account<'info, Rewarder>,
+}
+
+impl<'a, 'info> QuarryAdapter<'a, 'info> {
+    pub const NUMBER_OF_ACCOUNTS: usize = YiAdapter::NUMBER_OF_ACCOUNTS + 5;
+
+    pub fn load(pool_vault: PoolVault<'a, 'info>, remaining_accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        if remaining_accounts.len() < Self::NUMBER_OF_ACCOUNTS {
+            return Err(error!(SolscatterError::InvalidYieldSourceAccounts));
+        }
+
+        let yi = YiAdapter::load(pool_vault, remaining_accounts)?;
+        let quarry_accounts = &remaining_accounts[YiAdapter::NUMBER_OF_ACCOUNTS..];
+        let quarry_mine_program = quarry_accounts[0].clone();
+        let miner: Account<Miner> = Account::try_from(&quarry_accounts[1])?;
+        let quarry: Account<Quarry> = Account::try_from(&quarry_accounts[2])?;
+        let miner_vault: Account<TokenAccount> = Account::try_from(&quarry_accounts[3])?;
+        let rewarder: Account<Rewarder> = Account::try_from(&quarry_accounts[4])?;
+
+        let main_state = yi.pool_vault().main_state;
+        if quarry_mine_program.key() != quarry_mine::id()
+            || miner.key() != main_state.miner
+            || miner.authority != main_state.key()
+            || miner.quarry_key != quarry.key()
+            || miner.token_vault_key != miner_vault.key()
+            || quarry.rewarder_key != rewarder.key()
+            || quarry.token_mint_key != yi.yi_mint().key()
+        {
+            return Err(error!(SolscatterError::InvalidYieldSourceAccounts));
+        }
+
+        Ok(Self {
+            yi,
+            quarry_mine_program,
+            miner,
+            quarry,
+            miner_vault,
+            rewarder,
+        })
+    }
+
+    fn into_user_stake_context(&self) -> CpiContext<'_, '_, '_, 'info, UserStake<'info>> {
+        let pool_vault = self.yi.pool_vault();
+        CpiContext::new(
+            self.quarry_mine_program.clone(),
+            UserStake {
+                authority: pool_vault.main_state.to_account_info(),
+                miner: self.miner.to_account_info(),
+                quarry: self.quarry.to_account_info(),
+                miner_vault: self.miner_vault.to_account_info(),
+                token_account: pool_vault.vault.to_account_info(),
+                token_program: pool_vault.token_program.clone(),
+                rewarder: self.rewarder.to_account_info(),
+            },
+        )
+    }
+
+    /// Mines every yi token in the vault, including the ones just staked.
+    fn stake_vault(&self) -> Result<()> {
+        let pool_vault = self.yi.pool_vault();
+        let amount = token::accessor::amount(&pool_vault.vault.to_account_info())?;
+        let signer_seeds = pool_vault.signer_seeds();
+        quarry_mine::cpi::stake_tokens(
+            self.into_user_stake_context().with_signer(&[&signer_seeds]),
+            amount,
+        )
+    }
+
+    fn staked_amount(&self) -> u64 {
+        self.yi.pool_vault().vault.amount + self.miner_vault.amount
+    }
+}
+
+impl<'a, 'info> YieldSourceAdapter<'info> for QuarryAdapter<'a, 'info> {
+    fn deposit(&self, source_tokens: &AccountInfo<'info>, source_authority: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.yi.deposit(source_tokens, source_authority, amount)?;
+        self.stake_vault()
+    }
+
+    fn deposit_from_pool(&self, source_tokens: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.yi.deposit_from_pool(source_tokens, amount)?;
+        self.stake_vault()
+    }
+
+    fn withdraw(&self, destination_tokens: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let yi_amount = self.to_vault_amount(amount)?;
+        self.unstake_to_vault(yi_amount)?;
+        self.yi.unstake(destination_tokens, yi_amount)
+    }
+
+    fn current_value(&self) -> Result<u64> {
+        self.yi.calculate_underlying_amount(self.staked_amount())
+    }
+
+    fn to_vault_amount(&self, amount: u64) -> Result<u64> {
+        let yi_amount = self.yi.calculate_yi_amount(amount)?;
+        Ok(std::cmp::min(yi_amount, self.staked_amount()))
+    }
+
+    fn unstake_to_vault(&self, vault_amount: u64) -> Result<()> {
+        let amount = std::cmp::min(
+            vault_amount.saturating_sub(self.yi.pool_vault().vault.amount),
+            self.miner_vault.amount,
+        );
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let signer_seeds = self.yi.pool_vault().signer_seeds();
+        quarry_mine::cpi::withdraw_tokens(
+            self.into_user_stake_context().with_signer(&[&signer_seeds]),
+            amount,
+        )
+    }
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use yi::{
+    cpi::accounts::{Stake, Unstake},
+    YiToken,
+};
+
+use crate::error::SolscatterError;
+use crate::utils::{calculate_underlying_amount, calculate_yi_amount};
+use crate::yield_source::{PoolVault, YieldSourceAdapter};
+
+/// Stakes the underlying tokens into a [YiToken], the vault holds the minted yi tokens.
+///
+/// Remaining accounts:
+/// 0. `[]` yi token program
+/// 1. `[]` [YiToken], e.g. sol_ust_authority
+/// 2. `[writable]` [YiToken::mint]
+/// 3. `[writable]` [YiToken::underlying_tokens]
+pub struct YiAdapter<'a, 'info> {
+    pool_vault: PoolVault<'a, 'info>,
+    yi_token_program: AccountInfo<'info>,
+    yi_token: AccountLoader<'info, YiToken>,
+    yi_mint: Account<'info, Mint>,
+    yi_underlying_tokens: Account<'info, TokenAccount>,
+}
+
+impl<'a, 'info> YiAdapter<'a, 'info> {
+    pub const NUMBER_OF_ACCOUNTS: usize = 4;
+
+    pub fn load(pool_vault: PoolVault<'a, 'info>, remaining_accounts: &[AccountInfo<'info>]) -> Result<Self> {
+        if remaining_accounts.len() < Self::NUMBER_OF_ACCOUNTS {
+            return Err(error!(SolscatterError::InvalidYieldSourceAccounts));
+        }
+
+        let yi_token_program = remaining_accounts[0].clone();
+        let yi_token: AccountLoader<YiToken> = AccountLoader::try_from(&remaining_accounts[1])?;
+        let yi_mint: Account<Mint> = Account::try_from(&remaining_accounts[2])?;
+        let yi_underlying_tokens: Account<TokenAccount> = Account::try_from(&remaining_accounts[3])?;
+
+        {
+            let yi_token_data = yi_token.load()?;
+            if yi_token_program.key() != yi::program::Yi::id()
+                || yi_token_data.mint != yi_mint.key()
+                || yi_token_data.underlying_tokens != yi_underlying_tokens.key()
+                || yi_token_data.underlying_token_mint != pool_vault.main_state.underlying_mint
+                || pool_vault.vault.mint != yi_mint.key()
+            {
+                return Err(error!(SolscatterError::InvalidYieldSourceAccounts));
+            }
+        }
+
+        Ok(Self {
+            pool_vault,
+            yi_token_program,
+            yi_token,
+            yi_mint,
+            yi_underlying_tokens,
+        })
+    }
+
+    pub fn pool_vault(&self) -> &PoolVault<'a, 'info> {
+        &self.pool_vault
+    }
+
+    pub fn yi_mint(&self) -> &Account<'info, Mint> {
+        &self.yi_mint
+    }
+
+    /// Stakes `amount` into the vault, `signer_seeds` sign for a program-owned `source_authority`.
+    fn stake(
+        &self,
+        source_tokens: &AccountInfo<'info>,
+        source_authority: &AccountInfo<'info>,
+        amount: u64,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        yi::cpi::stake(
+            CpiContext::new(
+                self.yi_token_program.clone(),
+                Stake {
+                    yi_token: self.yi_token.to_account_info(),
+                    yi_mint: self.yi_mint.to_account_info(),
+                    source_tokens: source_tokens.clone(),
+                    source_authority: source_authority.clone(),
+                    yi_underlying_tokens: self.yi_underlying_tokens.to_account_info(),
+                    destination_yi_tokens: self.pool_vault.vault.to_account_info(),
+                    token_program: self.pool_vault.token_program.clone(),
+                },
+            )
+            .with_signer(signer_seeds),
+            amount,
+        )
+    }
+
+    /// Burns `yi_amount` from the vault, the unstaked tokens go to `destination_tokens`.
+    pub fn unstake(&self, destination_tokens: &AccountInfo<'info>, yi_amount: u64) -> Result<()> {
+        let signer_seeds = self.pool_vault.signer_seeds();
+        yi::cpi::unstake(
+            CpiContext::new(
+                self.yi_token_program.clone(),
+                Unstake {
+                    yi_token: self.yi_token.to_account_info(),
+                    yi_mint: self.yi_mint.to_account_info(),
+                    source_yi_tokens: self.pool_vault.vault.to_account_info(),
+                    source_authority: self.pool_vault.main_state.to_account_info(),
+                    yi_underlying_tokens: self.yi_underlying_tokens.to_account_info(),
+                    destination_underlying_tokens: destination_tokens.clone(),
+                    token_program: self.pool_vault.token_program.clone(),
+                },
+            )
+            .with_signer(&[&signer_seeds]),
+            yi_amount,
+        )
+    }
+
+    pub fn calculate_yi_amount(&self, amount: u64) -> Result<u64> {
+        calculate_yi_amount(
+            amount,
+            self.yi_underlying_tokens.amount,
+            self.yi_mint.supply,
+        )
+    }
+
+    pub fn calculate_underlying_amount(&self, yi_amount: u64) -> Result<u64> {
+        calculate_underlying_amount(
+            yi_amount,
+            self.yi_underlying_tokens.amount,
+            self.yi_mint.supply,
+        )
+    }
+}
+
+impl<'a, 'info> YieldSourceAdapter<'info> for YiAdapter<'a, 'info> {
+    fn deposit(&self, source_tokens: &AccountInfo<'info>, source_authority: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        self.stake(source_tokens, source_authority, amount, &[])
+    }
+
+    fn deposit_from_pool(&self, source_tokens: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let signer_seeds = self.pool_vault.signer_seeds();
+        self.stake(
+            source_tokens,
+            &self.pool_vault.main_state.to_account_info(),
+            amount,
+            &[&signer_seeds],
+        )
+    }
+
+    /// Only `amount` leaves the vault, the accrued yield stays with the protocol.
+    fn withdraw(&self, destination_tokens: &AccountInfo<'info>, amount: u64) -> Result<()> {
+        let yi_amount = self.to_vault_amount(amount)?;
+        self.unstake(destination_tokens, yi_amount)
+    }
+
+    fn current_value(&self) -> Result<u64> {
+        self.calculate_underlying_amount(self.pool_vault.vault.amount)
+    }
+
+    fn to_vault_amount(&self, amount: u64) -> Result<u64> {
+        let yi_amount = self.calculate_yi_amount(amount)?;
+        Ok(std::cmp::min(yi_amount, self.pool_vault.vault.amount))
+    }
+}
@@ -1,6 +1,13 @@
 import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
-import { Token } from "@solana/spl-token";
+import {
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  NATIVE_MINT,
+  transfer,
+} from "@solana/spl-token";
 import {
   Callback,
   loadSwitchboardProgram,
@@ -11,9 +18,14 @@ import {
 } from "@switchboard-xyz/switchboard-v2";
 import { assert } from "chai";
 import { Solscatter } from "../target/types/solscatter";
-import { loadKeypair } from "./utils";
-
-type OptionWinner = anchor.web3.PublicKey | null;
+import {
+  assertProgramError,
+  findSlot,
+  loadKeypair,
+  sleep,
+  waitUntil,
+  yiYieldSourceAccounts,
+} from "./utils";
 
 const STATE_SEED = "STATE";
 const DEVNET_CLUSTER = "devnet";
@@ -25,6 +37,216 @@ const YI_PROGRAM_ADDRESS = new anchor.web3.PublicKey("YiiTopEnX2vyoWdXuG45ovDFYZ
 const YI_MINT = new anchor.web3.PublicKey("6XyygxFmUeemaTvA9E9mhH9FvgpynZqARVyG3gUdCMt7");
 const YI_UNDERLYING_MINT = new anchor.web3.PublicKey("5fjG31cbSszE6FodW37UJnNzgVTyqg5WHWGCmL3ayAvA");
 const SOL_UST_AUTHORITY = new anchor.web3.PublicKey("8yazwmgc66uKrDBy3TZpNCgLa8qUDcuH8PZCz9jy6dzd");
+// 8 bytes discriminator + 2 trees of 8 bytes for each of the 65536 deposit slots
+const DEPOSIT_TREE_SPACE = 8 + 2 * 8 * 65536;
+const POOL_ID = new anchor.BN(0);
+// short enough for the drawing tests to wait for the first round to end
+const ROUND_DURATION_SECONDS = 120;
+// long enough to claim a prize right after the drawing, short enough to wait for the rest to expire
+const CLAIM_WINDOW_SECONDS = 60;
+const USER_SEED = "user";
+const WRAPPED_SOL_SEED = "wrapped_sol";
+
+async function findMainState(
+  program: anchor.Program<Solscatter>,
+  underlyingMint: anchor.web3.PublicKey,
+  poolId: anchor.BN
+): Promise<anchor.web3.PublicKey> {
+  const [mainState] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("main_state"), underlyingMint.toBuffer(), Buffer.from(poolId.toArray("le", 8))],
+    program.programId
+  );
+  return mainState;
+}
+
+// Fetches the yi pool the drawing tests run against, other pools share the program.
+async function fetchYiPool(program: anchor.Program<Solscatter>) {
+  const publicKey = await findMainState(program, YI_UNDERLYING_MINT, POOL_ID);
+  return { publicKey, account: await program.account.mainState.fetch(publicKey) };
+}
+
+async function findVrfClientState(
+  program: anchor.Program<Solscatter>,
+  vrf: anchor.web3.PublicKey,
+  mainState: anchor.web3.PublicKey
+): Promise<[anchor.web3.PublicKey, number]> {
+  return anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from(STATE_SEED), vrf.toBuffer(), mainState.toBuffer()],
+    program.programId
+  );
+}
+
+async function findUserDepositIndex(
+  program: anchor.Program<Solscatter>,
+  mainState: anchor.web3.PublicKey,
+  owner: anchor.web3.PublicKey
+): Promise<anchor.web3.PublicKey> {
+  const [userDepositIndex] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from(USER_SEED), mainState.toBuffer(), owner.toBuffer()],
+    program.programId
+  );
+  return userDepositIndex;
+}
+
+async function findUserDeposit(
+  program: anchor.Program<Solscatter>,
+  mainState: anchor.web3.PublicKey,
+  slot: anchor.BN
+): Promise<anchor.web3.PublicKey> {
+  const [userDeposit] = await anchor.web3.PublicKey.findProgramAddress(
+    [mainState.toBuffer(), Buffer.from(slot.toArray("le", 8))],
+    program.programId
+  );
+  return userDeposit;
+}
+
+async function findDrawingResult(
+  program: anchor.Program<Solscatter>,
+  mainState: anchor.web3.PublicKey,
+  round: anchor.BN
+): Promise<anchor.web3.PublicKey> {
+  const [drawingResult] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("drawing_result"), mainState.toBuffer(), Buffer.from(round.toArray("le", 8))],
+    program.programId
+  );
+  return drawingResult;
+}
+
+async function findWinnersPage(
+  program: anchor.Program<Solscatter>,
+  drawingResult: anchor.web3.PublicKey,
+  pageIndex: number
+): Promise<anchor.web3.PublicKey> {
+  const [winnersPage] = await anchor.web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from("drawing_winners"),
+      drawingResult.toBuffer(),
+      Buffer.from(new anchor.BN(pageIndex).toArray("le", 2)),
+    ],
+    program.programId
+  );
+  return winnersPage;
+}
+
+async function claimYiPrize(
+  program: anchor.Program<Solscatter>,
+  mainState: { publicKey: anchor.web3.PublicKey; account: { vault: anchor.web3.PublicKey } },
+  drawingResult: anchor.web3.PublicKey,
+  winnersPage: anchor.web3.PublicKey,
+  winner: anchor.web3.Keypair,
+  rewardIndex: number
+): Promise<string> {
+  const destinationTokens = await anchor.utils.token.associatedAddress({
+    mint: YI_UNDERLYING_MINT,
+    owner: winner.publicKey,
+  });
+  const yiUnderlyingTokens = await anchor.utils.token.associatedAddress({
+    mint: YI_UNDERLYING_MINT,
+    owner: SOL_UST_AUTHORITY,
+  });
+
+  return program.rpc.claimPrize(
+    { rewardIndex },
+    {
+      accounts: {
+        drawingResult,
+        winnersPage,
+        mainState: mainState.publicKey,
+        winner: winner.publicKey,
+        vault: mainState.account.vault,
+        destinationTokens,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      },
+      remainingAccounts: yiYieldSourceAccounts(
+        YI_PROGRAM_ADDRESS,
+        SOL_UST_AUTHORITY,
+        YI_MINT,
+        yiUnderlyingTokens
+      ),
+      signers: [winner],
+    }
+  );
+}
+
+// Initializes a pool that keeps its deposits in the vault, the vault mint being the underlying mint.
+async function initializeNoYieldPool(
+  program: anchor.Program<Solscatter>,
+  underlyingMint: anchor.web3.PublicKey,
+  poolId: anchor.BN,
+  vrf: anchor.web3.PublicKey
+): Promise<anchor.web3.PublicKey> {
+  const mainState = await findMainState(program, underlyingMint, poolId);
+  const [vrfClientState] = await findVrfClientState(program, vrf, mainState);
+  const [vault] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("vault"), mainState.toBuffer()],
+    program.programId
+  );
+  const [treasury] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("treasury"), mainState.toBuffer()],
+    program.programId
+  );
+
+  const depositTree = anchor.web3.Keypair.generate();
+  const createDepositTreeIx = anchor.web3.SystemProgram.createAccount({
+    fromPubkey: program.provider.wallet.publicKey,
+    newAccountPubkey: depositTree.publicKey,
+    space: DEPOSIT_TREE_SPACE,
+    lamports: await program.provider.connection.getMinimumBalanceForRentExemption(
+      DEPOSIT_TREE_SPACE
+    ),
+    programId: program.programId,
+  });
+
+  await program.rpc.initialize(
+    {
+      poolId,
+      yieldSource: { noYield: {} },
+      roundDuration: new anchor.BN(ROUND_DURATION_SECONDS),
+    },
+    {
+      accounts: {
+        mainState,
+        vrfClientState,
+        vrfAccountInfo: vrf,
+        underlyingMint,
+        vaultMint: underlyingMint,
+        vault,
+        treasury,
+        depositTree: depositTree.publicKey,
+        signer: program.provider.wallet.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      instructions: [createDepositTreeIx],
+      signers: [depositTree],
+    }
+  );
+  return mainState;
+}
+
+// Gives `user` a deposit in `mainState`, in the next free slot.
+async function initializeUserDeposit(
+  program: anchor.Program<Solscatter>,
+  mainState: anchor.web3.PublicKey,
+  user: anchor.web3.Keypair
+): Promise<anchor.web3.PublicKey> {
+  const { currentSlot } = await program.account.mainState.fetch(mainState);
+  const userDeposit = await findUserDeposit(program, mainState, currentSlot.addn(1));
+
+  await program.rpc.depositInitialize({
+    accounts: {
+      userDepositIndex: await findUserDepositIndex(program, mainState, user.publicKey),
+      userDeposit,
+      mainState,
+      depositor: user.publicKey,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    },
+    signers: [user],
+  });
+  return userDeposit;
+}
 
 async function createVrfAccount(
   program: anchor.Program<Solscatter>
@@ -36,12 +258,21 @@ async function createVrfAccount(
 
   console.log("wallet:", program.provider.wallet.publicKey.toBase58());
   console.log("vrfSecret:", vrfSecret.publicKey.toBase58());
+  const [mainStatePda] = await anchor.web3.PublicKey.findProgramAddress(
+    [
+      Buffer.from("main_state"),
+      YI_UNDERLYING_MINT.toBuffer(),
+      Buffer.from(POOL_ID.toArray("le", 8)),
+    ],
+    vrfClientProgram.programId
+  );
+
   const [stateAccountPda, stateBump] =
     await anchor.web3.PublicKey.findProgramAddress(
       [
         Buffer.from(STATE_SEED),
         vrfSecret.publicKey.toBuffer(),
-        program.provider.wallet.publicKey.toBuffer(),
+        mainStatePda.toBuffer(),
       ],
       vrfClientProgram.programId
     );
@@ -62,6 +293,7 @@ async function createVrfAccount(
     accounts: [
       { pubkey: stateAccountPda, isSigner: false, isWritable: true },
       { pubkey: vrfSecret.publicKey, isSigner: false, isWritable: false },
+      { pubkey: mainStatePda, isSigner: false, isWritable: false },
     ],
     ixData: ixCoder.encode("callbackRequestRandomness", ""),
   };
@@ -136,7 +368,11 @@ describe("solscatter", () => {
   it("Is initialized!", async () => {
     const vrfSecret = loadKeypair("./secrets/vrf-keypair.json");
     let [mainStatePda] = await anchor.web3.PublicKey.findProgramAddress(
-      [Buffer.from("main_state")],
+      [
+        Buffer.from("main_state"),
+        YI_UNDERLYING_MINT.toBuffer(),
+        Buffer.from(POOL_ID.toArray("le", 8)),
+      ],
       program.programId
     );
 
@@ -145,56 +381,101 @@ describe("solscatter", () => {
         [
           Buffer.from(STATE_SEED),
           vrfSecret.publicKey.toBuffer(),
-          program.provider.wallet.publicKey.toBuffer(),
+          mainStatePda.toBuffer(),
         ],
         program.programId
       );
 
-    const tx = await program.rpc.initialize({
-      accounts: {
-        mainState: mainStatePda,
-        vrfClientState: stateAccountPda,
-        vrfAccountInfo: vrfSecret.publicKey,
-        signer: program.provider.wallet.publicKey,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      },
+    const [vaultPda] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("vault"), mainStatePda.toBuffer()],
+      program.programId
+    );
+    const [treasuryPda] = await anchor.web3.PublicKey.findProgramAddress(
+      [Buffer.from("treasury"), mainStatePda.toBuffer()],
+      program.programId
+    );
+
+    const depositTree = anchor.web3.Keypair.generate();
+    const createDepositTreeIx = anchor.web3.SystemProgram.createAccount({
+      fromPubkey: program.provider.wallet.publicKey,
+      newAccountPubkey: depositTree.publicKey,
+      space: DEPOSIT_TREE_SPACE,
+      lamports: await program.provider.connection.getMinimumBalanceForRentExemption(
+        DEPOSIT_TREE_SPACE
+      ),
+      programId: program.programId,
     });
+
+    const tx = await program.rpc.initialize(
+      {
+        poolId: POOL_ID,
+        yieldSource: { yi: {} },
+        roundDuration: new anchor.BN(ROUND_DURATION_SECONDS),
+      },
+      {
+        accounts: {
+          mainState: mainStatePda,
+          vrfClientState: stateAccountPda,
+          vrfAccountInfo: vrfSecret.publicKey,
+          underlyingMint: YI_UNDERLYING_MINT,
+          vaultMint: YI_MINT,
+          vault: vaultPda,
+          treasury: treasuryPda,
+          depositTree: depositTree.publicKey,
+          signer: program.provider.wallet.publicKey,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        instructions: [createDepositTreeIx],
+        signers: [depositTree],
+      }
+    );
     console.log("Your transaction signature", tx);
   });
 
-  it("deposit initialize each user", async () => {
-    const mainState = (await program.account.mainState.all())[0];
-    let currentSlot = mainState.account.currentSlot;
+  it("update config", async () => {
+    const mainState = await fetchYiPool(program);
 
-    for (let user of users) {
-      currentSlot = currentSlot.add(new anchor.BN(1));
-      const [userDeposit] = await anchor.web3.PublicKey.findProgramAddress(
-        [Buffer.from(currentSlot.toArray("le", 8))],
-        program.programId
-      );
+    await program.rpc.updateConfig(
+      {
+        // 1 grand prize at 50%, 4 at 10% each
+        prizeTiers: [
+          { numberOfRewards: 1, shareBps: 5000 },
+          { numberOfRewards: 4, shareBps: 1000 },
+        ],
+        claimWindow: new anchor.BN(CLAIM_WINDOW_SECONDS),
+      },
+      {
+        accounts: {
+          mainState: mainState.publicKey,
+          admin: program.provider.wallet.publicKey,
+        },
+      }
+    );
 
-      const solUstATA = await anchor.utils.token.associatedAddress({
-        mint: YI_UNDERLYING_MINT,
-        owner: user.publicKey,
-      });
+    const updatedMainState = await program.account.mainState.fetch(mainState.publicKey);
+    assert.equal(updatedMainState.numberOfRewards, 5);
+    assert.equal(updatedMainState.prizeTiers.length, 2);
+    assert.isTrue(updatedMainState.claimWindow.eqn(CLAIM_WINDOW_SECONDS));
+  });
 
-      const yiSolUstATA = await anchor.utils.token.associatedAddress({
-        mint: YI_MINT,
-        owner: user.publicKey,
-      });
+  it("deposit initialize each user", async () => {
+    for (let user of users) {
+      const mainState = await fetchYiPool(program);
+      const userDepositIndex = await findUserDepositIndex(program, mainState.publicKey, user.publicKey);
+      // returning users keep their slot, new ones get the next one
+      const existingIndex = await program.account.userDepositIndex.fetchNullable(userDepositIndex);
+      const slot = existingIndex ? existingIndex.slot : mainState.account.currentSlot.add(new anchor.BN(1));
+      const userDeposit = await findUserDeposit(program, mainState.publicKey, slot);
 
       await program.rpc.depositInitialize({
         accounts: {
+          userDepositIndex,
           userDeposit,
           mainState: mainState.publicKey.toBase58(),
           depositor: user.publicKey,
-          yiUnderlyingMint: YI_UNDERLYING_MINT,
-          yiMint: YI_MINT,
-          solUstTokenAccount: solUstATA,
-          yiSolUstTokenAccount: yiSolUstATA,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
-          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: anchor.web3.SystemProgram.programId,
         },
         signers: [user],
@@ -202,15 +483,39 @@ describe("solscatter", () => {
     }
   });
 
-  it.only("deposit each user", async () => {
-    const mainState = (await program.account.mainState.all())[0];
-    let currentSlot = new anchor.BN(1);
+  it("deposit initialize keeps the slot of a returning user", async () => {
+    const user = users[0];
+    const mainState = await fetchYiPool(program);
+    const userDepositIndex = await findUserDepositIndex(program, mainState.publicKey, user.publicKey);
+    const { slot } = await program.account.userDepositIndex.fetch(userDepositIndex);
+    const userDeposit = await findUserDeposit(program, mainState.publicKey, slot);
+
+    await program.rpc.depositInitialize({
+      accounts: {
+        userDepositIndex,
+        userDeposit,
+        mainState: mainState.publicKey,
+        depositor: user.publicKey,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [user],
+    });
+
+    const updatedMainState = await program.account.mainState.fetch(mainState.publicKey);
+    assert.isTrue(updatedMainState.currentSlot.eq(mainState.account.currentSlot));
+    const updatedIndex = await program.account.userDepositIndex.fetch(userDepositIndex);
+    assert.isTrue(updatedIndex.slot.eq(slot));
+  });
+
+  it("deposit each user", async () => {
+    const mainState = await fetchYiPool(program);
 
     for (let user of users) {
-      const [userDeposit] = await anchor.web3.PublicKey.findProgramAddress(
-        [Buffer.from(currentSlot.toArray("le", 8))],
-        program.programId
+      const userDepositIndex = await program.account.userDepositIndex.fetch(
+        await findUserDepositIndex(program, mainState.publicKey, user.publicKey)
       );
+      const userDeposit = await findUserDeposit(program, mainState.publicKey, userDepositIndex.slot);
 
       // const randomAmountBetween100To500 = Math.floor(
       //   Math.random() * (500 - 100) + 100
@@ -232,10 +537,6 @@ describe("solscatter", () => {
         owner: SOL_UST_AUTHORITY,
       });
 
-      const destinationYiTokens = await anchor.utils.token.associatedAddress({
-        mint: YI_MINT,
-        owner: user.publicKey,
-      });
 
       await program.rpc.deposit(
         {
@@ -245,38 +546,92 @@ describe("solscatter", () => {
           accounts: {
             userDeposit,
             mainState: mainState.publicKey,
+            depositTree: mainState.account.depositTree,
             owner: user.publicKey,
             clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
 
-            yiTokenProgram: YI_PROGRAM_ADDRESS,
-            solUstAuthority: SOL_UST_AUTHORITY,
-            yiMint: YI_MINT,
             sourceTokens: sourceTokens,
             sourceAuthority: user.publicKey,
-            yiUnderlyingTokens: yiUnderlyingTokens,
-            destinationYiTokens: destinationYiTokens,
+            vault: mainState.account.vault,
             tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           },
+          remainingAccounts: yiYieldSourceAccounts(
+            YI_PROGRAM_ADDRESS,
+            SOL_UST_AUTHORITY,
+            YI_MINT,
+            yiUnderlyingTokens
+          ),
           signers: [user],
         }
       );
+    }
+  });
+
+  it("withdraw each user", async () => {
+    const mainState = await fetchYiPool(program);
+
+    for (let user of users) {
+      const userDepositIndex = await program.account.userDepositIndex.fetch(
+        await findUserDepositIndex(program, mainState.publicKey, user.publicKey)
+      );
+      const userDeposit = await findUserDeposit(program, mainState.publicKey, userDepositIndex.slot);
 
-      currentSlot = currentSlot.add(new anchor.BN(1));
+      const userDepositBefore = await program.account.userDeposit.fetch(userDeposit);
+      const withdrawAmount = userDepositBefore.amount.div(new anchor.BN(2));
+
+      const yiUnderlyingTokens = await anchor.utils.token.associatedAddress({
+        mint: YI_UNDERLYING_MINT,
+        owner: SOL_UST_AUTHORITY,
+      });
+
+      const destinationTokens = await anchor.utils.token.associatedAddress({
+        mint: YI_UNDERLYING_MINT,
+        owner: user.publicKey,
+      });
+
+      await program.rpc.withdraw(
+        {
+          amount: withdrawAmount,
+        },
+        {
+          accounts: {
+            userDeposit,
+            mainState: mainState.publicKey,
+            depositTree: mainState.account.depositTree,
+            owner: user.publicKey,
+
+            vault: mainState.account.vault,
+            destinationTokens,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          },
+          remainingAccounts: yiYieldSourceAccounts(
+            YI_PROGRAM_ADDRESS,
+            SOL_UST_AUTHORITY,
+            YI_MINT,
+            yiUnderlyingTokens
+          ),
+          signers: [user],
+        }
+      );
+
+      const userDepositAfter = await program.account.userDeposit.fetch(userDeposit);
+      assert.isTrue(
+        userDepositAfter.amount.eq(userDepositBefore.amount.sub(withdrawAmount))
+      );
     }
   });
 
   it("request randomness", async () => {
     const vrfSecret = loadKeypair("./secrets/vrf-keypair.json");
     const switchboardProgram = await loadSwitchboardProgram(DEVNET_CLUSTER);
+    const mainState = await fetchYiPool(program);
+    // only a result produced after the round ended can seed its drawing
+    await waitUntil(program.provider.connection, mainState.account.roundEndTimestamp);
 
-    const [stateAccountPda, stateBump] =
-    await anchor.web3.PublicKey.findProgramAddress(
-      [
-        Buffer.from(STATE_SEED),
-        vrfSecret.publicKey.toBuffer(),
-        program.provider.wallet.publicKey.toBuffer(),
-      ],
-      program.programId
+    const [stateAccountPda, stateBump] = await findVrfClientState(
+      program,
+      vrfSecret.publicKey,
+      mainState.publicKey
     );
 
     const vrfAccount = new VrfAccount({
@@ -284,7 +639,10 @@ describe("solscatter", () => {
       publicKey: vrfSecret.publicKey,
     });
 
-    const vrfClientState = (await program.account.vrfClientState.all())[0];
+    const vrfClientState = {
+      publicKey: stateAccountPda,
+      account: await program.account.vrfClientState.fetch(stateAccountPda),
+    };
     const vrf = await vrfAccount.loadData(); 
     const queueAccount = new OracleQueueAccount({
       program: switchboardProgram,
@@ -322,7 +680,8 @@ describe("solscatter", () => {
     {
       accounts: {
         state: vrfClientState.publicKey,
-        authority: program.provider.wallet.publicKey,
+        mainState: mainState.publicKey,
+        admin: program.provider.wallet.publicKey,
         switchboardProgram: switchboardProgram.programId,
         vrf: vrfClientState.account.vrf,
         oracleQueue: queueAccount.publicKey,
@@ -341,111 +700,411 @@ describe("solscatter", () => {
     console.log(`https://solscan.io/tx/${requestTxn}?cluster=devnet`);
   });
 
-  // it("start drawing phase", async () => {
-  //   const mainState = (await program.account.mainState.all())[0];
-  //   const [drawingResultPda] = await anchor.web3.PublicKey.findProgramAddress(
-  //     [
-  //       Buffer.from("drawing_result"),
-  //       Buffer.from(mainState.account.currentRound.toArray("le", 8)),
-  //     ],
-  //     program.programId
-  //   );
-
-  //   const numberOfRewards = 5;
-  //   const randomNumbers: anchor.BN[] = [];
-  //   for (let i = 0; i < numberOfRewards; i++) {
-  //     randomNumbers[i] = new anchor.BN(
-  //       Math.floor(Math.random() * mainState.account.totalDeposit.toNumber())
-  //     );
-  //   }
-  //   console.log("numberOfRewards:", numberOfRewards);
-  //   console.log(
-  //     "randomNumbers:",
-  //     randomNumbers.map((randomNumber) => randomNumber.toString())
-  //   );
-
-  //   await program.rpc.startDrawingPhase(numberOfRewards, randomNumbers, {
-  //     accounts: {
-  //       drawingResult: drawingResultPda,
-  //       mainState: mainState.publicKey,
-  //       signer: program.provider.wallet.publicKey,
-  //       systemProgram: anchor.web3.SystemProgram.programId,
-  //     },
-  //   });
-  // });
+  it("start drawing phase", async () => {
+    const mainState = await fetchYiPool(program);
+    const [vrfClientState] = await findVrfClientState(
+      program,
+      mainState.account.vrfAccountPubkey,
+      mainState.publicKey
+    );
+    // the oracle answers through callback_request_randomness a few slots after the request
+    for (;;) {
+      const state = await program.account.vrfClientState.fetch(vrfClientState);
+      if (state.lastTimestamp.gte(mainState.account.roundEndTimestamp)) {
+        break;
+      }
+      await sleep(2000);
+    }
 
-  // it("drawing each user", async () => {
-  //   const mainState = (await program.account.mainState.all())[0];
-  //   const [drawingResultPda] = await anchor.web3.PublicKey.findProgramAddress(
-  //     [
-  //       Buffer.from("drawing_result"),
-  //       Buffer.from(mainState.account.currentRound.toArray("le", 8)),
-  //     ],
-  //     program.programId
-  //   );
-
-  //   let drawingResult = await program.account.drawingResult.fetch(
-  //     drawingResultPda
-  //   );
-  //   let processSlot = drawingResult.lastProcessedSlot;
-
-  //   let totalFees = 0;
-
-  //   for (let user of users) {
-  //     processSlot = processSlot.add(new anchor.BN(1));
-  //     const [userDepositPda] = await anchor.web3.PublicKey.findProgramAddress(
-  //       [Buffer.from(processSlot.toArray("le", 8))],
-  //       program.programId
-  //     );
-
-  //     const tx = await program.rpc.drawing({
-  //       accounts: {
-  //         mainState: mainState.publicKey,
-  //         drawingResult: drawingResultPda,
-  //         userDeposit: userDepositPda,
-  //         clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-  //       },
-  //     });
-
-  //     await program.provider.connection.confirmTransaction(tx, "confirmed");
-  //     const fee = (
-  //       await program.provider.connection.getTransaction(tx, {
-  //         commitment: "confirmed",
-  //       })
-  //     ).meta.fee;
-  //     totalFees = totalFees + fee;
-
-  //     console.log(
-  //       "processSlot: %s \n\t drawing: %s \n\t tx: %s",
-  //       processSlot.toString(),
-  //       user.publicKey.toBase58(),
-  //       tx
-  //     );
-
-  //     drawingResult = await program.account.drawingResult.fetch(
-  //       drawingResultPda
-  //     );
-
-  //     const drawWinners = drawingResult.winners as OptionWinner[];
-  //     const haveAllWinners =
-  //       drawWinners
-  //         .map((winner) => winner !== null)
-  //         .filter((isHaveWinner) => isHaveWinner).length ===
-  //       drawingResult.numberOfRewards;
-  //     if (haveAllWinners) {
-  //       break;
-  //     }
-  //   }
-
-  //   drawingResult = await program.account.drawingResult.fetch(drawingResultPda);
-  //   (drawingResult.winners as OptionWinner[])
-  //     .map((winner) => winner.toBase58())
-  //     .forEach((winner) => console.log("winner:", winner));
-
-  //   console.log("\ntotalFees: %s SOL", totalFees * 0.000000001);
-  //   console.log(
-  //     "finished_timestamp: %s\n",
-  //     drawingResult.finishedTimestamp.toString()
-  //   );
-  // });
+    const drawingResultPda = await findDrawingResult(
+      program,
+      mainState.publicKey,
+      mainState.account.currentRound
+    );
+    console.log("numberOfRewards:", mainState.account.numberOfRewards);
+
+    await program.rpc.startDrawingPhase({
+      accounts: {
+        drawingResult: drawingResultPda,
+        mainState: mainState.publicKey,
+        vrfClientState,
+        depositTree: mainState.account.depositTree,
+        admin: program.provider.wallet.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    });
+
+    const drawingResult = await program.account.drawingResult.fetch(drawingResultPda);
+    assert.deepEqual(drawingResult.state, { processing: {} });
+    assert.equal(drawingResult.numberOfRewards, mainState.account.numberOfRewards);
+    assert.equal(drawingResult.drawnRewards, 0);
+    assert.isTrue(drawingResult.totalTwab.gtn(0));
+    const updatedMainState = await program.account.mainState.fetch(mainState.publicKey);
+    assert.isTrue(updatedMainState.drawingInProgress);
+  });
+
+  it("drawing", async () => {
+    const mainState = await fetchYiPool(program);
+    const drawingResultPda = await findDrawingResult(
+      program,
+      mainState.publicKey,
+      mainState.account.currentRound
+    );
+
+    let drawingResult = await program.account.drawingResult.fetch(
+      drawingResultPda
+    );
+    const depositTree = await program.account.depositTree.fetch(
+      mainState.account.depositTree
+    );
+    const userDepositOwners = new Map<string, anchor.web3.PublicKey>();
+
+    for (let pageIndex = 0; pageIndex < drawingResult.numberOfPages; pageIndex++) {
+      const winnersPagePda = await findWinnersPage(program, drawingResultPda, pageIndex);
+
+      await program.rpc.initializeWinnersPage({
+        accounts: {
+          drawingResult: drawingResultPda,
+          winnersPage: winnersPagePda,
+          payer: program.provider.wallet.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+      });
+
+      const winnersPage = await program.account.drawingWinnersPage.fetch(
+        winnersPagePda
+      );
+      const entries = (winnersPage.entries as any[]).slice(
+        0,
+        winnersPage.numberOfEntries
+      );
+      // every winning deposit is passed once, however many rewards it wins
+      const winnerUserDeposits = new Map<string, anchor.web3.PublicKey>();
+      for (const entry of entries) {
+        const slot = findSlot(depositTree.twabs as anchor.BN[], entry.randomNumber);
+        const userDeposit = await findUserDeposit(program, mainState.publicKey, slot);
+        winnerUserDeposits.set(userDeposit.toBase58(), userDeposit);
+      }
+
+      const tx = await program.rpc.drawing({
+        accounts: {
+          mainState: mainState.publicKey,
+          drawingResult: drawingResultPda,
+          winnersPage: winnersPagePda,
+          depositTree: mainState.account.depositTree,
+          clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        },
+        remainingAccounts: [...winnerUserDeposits.values()].map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        })),
+      });
+      console.log("drawing tx:", tx);
+
+      for (const userDeposit of winnerUserDeposits.values()) {
+        const { owner } = await program.account.userDeposit.fetch(userDeposit);
+        userDepositOwners.set(owner.toBase58(), owner);
+      }
+
+      const drawnPage = await program.account.drawingWinnersPage.fetch(
+        winnersPagePda
+      );
+      (drawnPage.entries as { winner: anchor.web3.PublicKey; drawn: number }[])
+        .slice(0, drawnPage.numberOfEntries)
+        .forEach((entry) => {
+          assert.equal(entry.drawn, 1);
+          assert.isTrue(userDepositOwners.has(entry.winner.toBase58()));
+          console.log("winner:", entry.winner.toBase58());
+        });
+    }
+
+    drawingResult = await program.account.drawingResult.fetch(drawingResultPda);
+    assert.deepEqual(drawingResult.state, { finished: {} });
+    assert.equal(drawingResult.drawnRewards, drawingResult.numberOfRewards);
+    console.log(
+      "finished_timestamp: %s\n",
+      drawingResult.finishedTimestamp.toString()
+    );
+
+    const updatedMainState = await program.account.mainState.fetch(mainState.publicKey);
+    assert.isFalse(updatedMainState.drawingInProgress);
+    assert.isTrue(updatedMainState.currentRound.eq(mainState.account.currentRound.addn(1)));
+  });
+
+  it("claim prize", async () => {
+    const mainState = await fetchYiPool(program);
+    // the drawing above moved the pool on to the next round
+    const drawingResultPda = await findDrawingResult(
+      program,
+      mainState.publicKey,
+      mainState.account.currentRound.subn(1)
+    );
+    const winnersPagePda = await findWinnersPage(program, drawingResultPda, 0);
+    const winnersPage = await program.account.drawingWinnersPage.fetch(winnersPagePda);
+    const rewardIndex = 0;
+    const entry = (winnersPage.entries as any[])[rewardIndex];
+    const winner = users.find((user) => user.publicKey.equals(entry.winner));
+
+    const claimPrize = () =>
+      claimYiPrize(program, mainState, drawingResultPda, winnersPagePda, winner, rewardIndex);
+
+    await claimPrize();
+
+    const claimedPage = await program.account.drawingWinnersPage.fetch(winnersPagePda);
+    assert.equal((claimedPage.entries as any[])[rewardIndex].claimed, 1);
+    const updatedMainState = await program.account.mainState.fetch(mainState.publicKey);
+    assert.isTrue(
+      updatedMainState.unclaimedPrize.eq(mainState.account.unclaimedPrize.sub(entry.amount))
+    );
+
+    await assertProgramError(claimPrize(), "PrizeAlreadyClaimed");
+  });
+
+  it("sweep unclaimed prizes once the claim window closes", async () => {
+    const mainState = await fetchYiPool(program);
+    const drawingResultPda = await findDrawingResult(
+      program,
+      mainState.publicKey,
+      mainState.account.currentRound.subn(1)
+    );
+    const winnersPagePda = await findWinnersPage(program, drawingResultPda, 0);
+    const drawingResult = await program.account.drawingResult.fetch(drawingResultPda);
+    const winnersPage = await program.account.drawingWinnersPage.fetch(winnersPagePda);
+    const entries = (winnersPage.entries as any[]).slice(0, winnersPage.numberOfEntries);
+    const unclaimedAmount = entries
+      .filter((entry) => entry.claimed === 0)
+      .reduce((sum, entry) => sum.add(entry.amount), new anchor.BN(0));
+
+    await assertProgramError(
+      program.rpc.sweepUnclaimed({
+        accounts: {
+          mainState: mainState.publicKey,
+          drawingResult: drawingResultPda,
+          winnersPage: winnersPagePda,
+        },
+      }),
+      "ClaimWindowOpen"
+    );
+
+    await waitUntil(
+      program.provider.connection,
+      drawingResult.finishedTimestamp.add(drawingResult.claimWindow)
+    );
+
+    const rewardIndex = entries.findIndex((entry) => entry.claimed === 0);
+    const winner = users.find((user) => user.publicKey.equals(entries[rewardIndex].winner));
+    await assertProgramError(
+      claimYiPrize(program, mainState, drawingResultPda, winnersPagePda, winner, rewardIndex),
+      "ClaimExpired"
+    );
+
+    await program.rpc.sweepUnclaimed({
+      accounts: {
+        mainState: mainState.publicKey,
+        drawingResult: drawingResultPda,
+        winnersPage: winnersPagePda,
+      },
+    });
+
+    const sweptResult = await program.account.drawingResult.fetch(drawingResultPda);
+    assert.isTrue(sweptResult.expiredAmount.eq(unclaimedAmount));
+    const updatedMainState = await program.account.mainState.fetch(mainState.publicKey);
+    assert.isTrue(
+      updatedMainState.rolloverAmount.eq(mainState.account.rolloverAmount.add(unclaimedAmount))
+    );
+    assert.isTrue(
+      updatedMainState.unclaimedPrize.eq(mainState.account.unclaimedPrize.sub(unclaimedAmount))
+    );
+  });
+
+  describe("no yield pool", () => {
+    const user = users[0];
+    const payer = program.provider.wallet["payer"] as anchor.web3.Keypair;
+    const connection = program.provider.connection;
+    let underlyingMint: anchor.web3.PublicKey;
+    let mainState: anchor.web3.PublicKey;
+    let userDeposit: anchor.web3.PublicKey;
+    let userTokens: anchor.web3.PublicKey;
+
+    const vaultAmount = async (): Promise<number> => {
+      const { vault } = await program.account.mainState.fetch(mainState);
+      return Number((await getAccount(connection, vault)).amount);
+    };
+
+    it("is initialized over a new mint", async () => {
+      underlyingMint = await createMint(connection, payer, payer.publicKey, null, 6);
+      userTokens = (
+        await getOrCreateAssociatedTokenAccount(connection, payer, underlyingMint, user.publicKey)
+      ).address;
+      await mintTo(connection, payer, underlyingMint, userTokens, payer, 1e6);
+
+      const vrfSecret = loadKeypair("./secrets/vrf-keypair.json");
+      mainState = await initializeNoYieldPool(program, underlyingMint, POOL_ID, vrfSecret.publicKey);
+      userDeposit = await initializeUserDeposit(program, mainState, user);
+
+      const pool = await program.account.mainState.fetch(mainState);
+      assert.deepEqual(pool.yieldSource, { noYield: {} });
+      assert.isTrue(pool.underlyingMint.equals(underlyingMint));
+    });
+
+    it("keeps deposits in the vault as they are", async () => {
+      const { vault, depositTree } = await program.account.mainState.fetch(mainState);
+
+      await program.rpc.deposit(
+        { amount: new anchor.BN(6e5) },
+        {
+          accounts: {
+            userDeposit,
+            mainState,
+            depositTree,
+            owner: user.publicKey,
+            clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+            sourceTokens: userTokens,
+            sourceAuthority: user.publicKey,
+            vault,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          },
+          signers: [user],
+        }
+      );
+
+      assert.equal(await vaultAmount(), 6e5);
+      assert.equal(Number((await getAccount(connection, userTokens)).amount), 4e5);
+      const pool = await program.account.mainState.fetch(mainState);
+      assert.isTrue(pool.totalDeposit.eqn(6e5));
+    });
+
+    it("pays withdrawals out of the vault", async () => {
+      const { vault, depositTree } = await program.account.mainState.fetch(mainState);
+
+      await program.rpc.withdraw(
+        { amount: new anchor.BN(2e5) },
+        {
+          accounts: {
+            userDeposit,
+            mainState,
+            depositTree,
+            owner: user.publicKey,
+            vault,
+            destinationTokens: userTokens,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          },
+          signers: [user],
+        }
+      );
+
+      assert.equal(await vaultAmount(), 4e5);
+      assert.equal(Number((await getAccount(connection, userTokens)).amount), 6e5);
+      const { amount } = await program.account.userDeposit.fetch(userDeposit);
+      assert.isTrue(amount.eqn(4e5));
+    });
+
+    it("harvests tokens sent to the vault into the prize pool", async () => {
+      const { vault, treasury } = await program.account.mainState.fetch(mainState);
+      // the vault earns nothing by itself, so the prize is sent to it directly
+      await transfer(connection, payer, userTokens, vault, user, 1e5);
+
+      await program.rpc.harvest({
+        accounts: {
+          mainState,
+          vault,
+          treasury,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        },
+      });
+
+      assert.equal(await vaultAmount(), 5e5);
+      const pool = await program.account.mainState.fetch(mainState);
+      assert.isTrue(pool.prizePool.eqn(1e5));
+      assert.isTrue(pool.totalDeposit.eqn(4e5));
+    });
+  });
+
+  describe("sol pool", () => {
+    const user = users[0];
+    const connection = program.provider.connection;
+    // native mint pools outlive a test run on devnet, so each run gets its own
+    const poolId = new anchor.BN(Date.now());
+    let mainState: anchor.web3.PublicKey;
+    let userDeposit: anchor.web3.PublicKey;
+    let wrappedSolTokens: anchor.web3.PublicKey;
+
+    const vaultAmount = async (): Promise<number> => {
+      const { vault } = await program.account.mainState.fetch(mainState);
+      return Number((await getAccount(connection, vault)).amount);
+    };
+
+    it("is initialized over the native mint", async () => {
+      const vrfSecret = loadKeypair("./secrets/vrf-keypair.json");
+      mainState = await initializeNoYieldPool(program, NATIVE_MINT, poolId, vrfSecret.publicKey);
+      userDeposit = await initializeUserDeposit(program, mainState, user);
+      [wrappedSolTokens] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from(WRAPPED_SOL_SEED), userDeposit.toBuffer()],
+        program.programId
+      );
+    });
+
+    it("wraps deposited lamports into the vault", async () => {
+      const { vault, depositTree } = await program.account.mainState.fetch(mainState);
+
+      await program.rpc.depositSol(
+        { amount: new anchor.BN(0.1 * anchor.web3.LAMPORTS_PER_SOL) },
+        {
+          accounts: {
+            userDeposit,
+            mainState,
+            depositTree,
+            owner: user.publicKey,
+            wrappedSolTokens,
+            nativeMint: NATIVE_MINT,
+            vault,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          },
+          signers: [user],
+        }
+      );
+
+      assert.equal(await vaultAmount(), 0.1 * anchor.web3.LAMPORTS_PER_SOL);
+      assert.isNull(await connection.getAccountInfo(wrappedSolTokens));
+      const { amount } = await program.account.userDeposit.fetch(userDeposit);
+      assert.isTrue(amount.eqn(0.1 * anchor.web3.LAMPORTS_PER_SOL));
+    });
+
+    it("unwraps withdrawals into lamports", async () => {
+      const { vault, depositTree } = await program.account.mainState.fetch(mainState);
+      const lamportsBefore = await connection.getBalance(user.publicKey);
+
+      await program.rpc.withdrawSol(
+        { amount: new anchor.BN(0.04 * anchor.web3.LAMPORTS_PER_SOL) },
+        {
+          accounts: {
+            userDeposit,
+            mainState,
+            depositTree,
+            owner: user.publicKey,
+            wrappedSolTokens,
+            nativeMint: NATIVE_MINT,
+            vault,
+            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          },
+          signers: [user],
+        }
+      );
+
+      assert.equal(await vaultAmount(), 0.06 * anchor.web3.LAMPORTS_PER_SOL);
+      assert.isNull(await connection.getAccountInfo(wrappedSolTokens));
+      // the wSOL account rent comes back on close and the wallet pays the fee
+      assert.equal(
+        (await connection.getBalance(user.publicKey)) - lamportsBefore,
+        0.04 * anchor.web3.LAMPORTS_PER_SOL
+      );
+      const { amount } = await program.account.userDeposit.fetch(userDeposit);
+      assert.isTrue(amount.eqn(0.06 * anchor.web3.LAMPORTS_PER_SOL));
+    });
+  });
 });
@@ -0,0 +1,18 @@
+import * as anchor from "@project-serum/anchor";
+
+export const sleep = (ms: number): Promise<void> =>
+  new Promise((resolve) => setTimeout(resolve, ms));
+
+// Waits until the cluster's block time reaches `timestamp`, in unix seconds.
+export const waitUntil = async (
+  connection: anchor.web3.Connection,
+  timestamp: anchor.BN
+): Promise<void> => {
+  for (;;) {
+    const blockTime = await connection.getBlockTime(await connection.getSlot());
+    if (blockTime !== null && blockTime >= timestamp.toNumber()) {
+      return;
+    }
+    await sleep(2000);
+  }
+};
@@ -0,0 +1,20 @@
+import * as anchor from "@project-serum/anchor";
+
+export const DEPOSIT_TREE_CAPACITY = 65536;
+
+// Mirrors DepositTree::find_slot on-chain, nodes[i - 1] holds the partial TWAB sum for slot i.
+export const findSlot = (
+  nodes: anchor.BN[],
+  randomNumber: anchor.BN
+): anchor.BN => {
+  let position = 0;
+  let remaining = randomNumber;
+  for (let step = DEPOSIT_TREE_CAPACITY; step > 0; step >>= 1) {
+    const next = position + step;
+    if (next <= DEPOSIT_TREE_CAPACITY && nodes[next - 1].lte(remaining)) {
+      position = next;
+      remaining = remaining.sub(nodes[next - 1]);
+    }
+  }
+  return new anchor.BN(position + 1);
+};
@@ -0,0 +1,16 @@
+import { assert } from "chai";
+
+// Asserts that `promise` fails with the program error named `code`.
+export const assertProgramError = async (
+  promise: Promise<unknown>,
+  code: string
+): Promise<void> => {
+  let error: any = null;
+  try {
+    await promise;
+  } catch (err) {
+    error = err;
+  }
+  assert.isNotNull(error, `expected ${code}`);
+  assert.equal(error.error?.errorCode?.code ?? error.msg, code);
+};
@@ -1 +1,5 @@
-export * from "./keypair";
\ No newline at end of file
+export * from "./keypair";
+export * from "./clock";
+export * from "./errors";
+export * from "./deposit-tree";
+export * from "./yield-source";
\ No newline at end of file
@@ -0,0 +1,14 @@
+import * as anchor from "@project-serum/anchor";
+
+// Remaining accounts expected by the yi yield source adapter, in order.
+export const yiYieldSourceAccounts = (
+  yiProgram: anchor.web3.PublicKey,
+  yiToken: anchor.web3.PublicKey,
+  yiMint: anchor.web3.PublicKey,
+  yiUnderlyingTokens: anchor.web3.PublicKey
+): anchor.web3.AccountMeta[] => [
+  { pubkey: yiProgram, isSigner: false, isWritable: false },
+  { pubkey: yiToken, isSigner: false, isWritable: false },
+  { pubkey: yiMint, isSigner: false, isWritable: true },
+  { pubkey: yiUnderlyingTokens, isSigner: false, isWritable: true },
+];